@@ -1,19 +1,27 @@
-use std::{
-    fs::File,
-    io::{BufReader, LineWriter},
-};
+use std::io::LineWriter;
 
 use braille::Opt;
 
 fn main() -> anyhow::Result<()> {
-    // braille -G-1:1 -g
-    let file = File::open(concat!(env!("CARGO_MANIFEST_DIR"), "/rose.txt"))?;
-    let mut stdin = BufReader::new(file);
-    // let mut stdin = std::io::stdin().lock();
-    let opt = Opt::try_new_from_reader(&mut stdin, ["braille", "-m", "--use-full-default-height"])?;
+    // A rose with 8 petals (r = cos 4θ), given as an angle and radius on each line
+    let input: String = (0..=628)
+        .map(|i| {
+            let theta = f64::from(i) / 100.;
+            format!("{theta} {}\n", (4. * theta).cos())
+        })
+        .collect();
+
+    // braille -G-1:1 -g --polar
+    let opt = Opt::try_new([
+        "braille",
+        "-G-1:1",
+        "-g",
+        "--polar",
+        "--use-full-default-height",
+    ])?;
     let writer = LineWriter::new(std::io::stdout());
 
-    braille::grid::print_graph(opt, stdin, writer)?;
+    braille::grid::print_graph(opt, input.as_bytes(), writer)?;
 
     Ok(())
 }
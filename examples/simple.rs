@@ -1,7 +1,7 @@
 use std::collections::HashSet as Set;
 // use std::collections::BTreeSet as Set;
 
-use braillefb::Framebuffer;
+use braille::Canvas;
 
 fn scale(value: f64, i_min: f64, i_max: f64, f_min: f64, f_max: f64) -> f64 {
     debug_assert!(i_min < i_max);
@@ -39,19 +39,16 @@ fn main() -> anyhow::Result<()> {
     // let height = 29;
     let width = width * 2;
     let height = height * 4;
-    // let mut dots = get_input(width, height)?;
-    let mut dots = get_rose(width.into(), height.into());
-
-    let mut bits: Vec<bool> = Vec::with_capacity(usize::from(width * height));
-    for y in (0..height).rev() {
-        for x in 0..width {
-            bits.push(dots.remove(&Dot::new(x, y)));
-        }
-    }
+    // let dots = get_input(width, height)?;
+    let dots = get_rose(width.into(), height.into());
 
-    let fb = Framebuffer::new(&bits, width.into(), height.into());
+    let mut canvas = Canvas::try_new(width.into(), height.into())?;
+    for dot in dots {
+        // Dots count up from the bottom, but the canvas counts down from the top
+        canvas.set(i32::from(dot.x), i32::from(height - 1 - dot.y));
+    }
 
-    print!("{fb}");
+    print!("{canvas}");
 
     Ok(())
 }
@@ -0,0 +1,262 @@
+//! Labels for the smallest, largest, and last points of a graph.
+//!
+//! Each label has an arrow pointing at the character its point is drawn in, followed by the
+//! point's value. Labels are only written over empty space, so if there isn't room next to a
+//! point, it's left without one.
+//!
+//! # Example
+//!
+//! ```console
+//! $ printf '%s\n' 1 3 8 4 2 5 | braille -C --annotate 4
+//!   █←8
+//!   █  ▂←5
+//!  ▁█▆ █
+//! ▁███▅█
+//! ```
+//!
+//! The smallest value (1) is surrounded by the rest of the graph, so it isn't labelled.
+
+use crate::units::{self, Unit};
+
+/// Where a point is drawn in a graph, and what it's labelled with
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mark {
+    pub row: usize,
+    pub column: usize,
+    pub label: String,
+}
+
+/// Find the largest, smallest, and last of the points, in that order
+///
+/// Points are compared by `value`, and any point that's more than one of these is only given
+/// once.
+pub fn extremes<T: Copy + PartialEq>(
+    points: impl IntoIterator<Item = T>,
+    value: impl Fn(T) -> f64,
+) -> Vec<T> {
+    let mut extremes = Extremes::new(value);
+    for point in points {
+        extremes.push(point);
+    }
+
+    extremes.into_points()
+}
+
+/// The largest, smallest, and last of the points seen so far, for points that are only seen once
+pub struct Extremes<T, F> {
+    value: F,
+    max: Option<T>,
+    min: Option<T>,
+    last: Option<T>,
+}
+
+impl<T: Copy + PartialEq, F: Fn(T) -> f64> Extremes<T, F> {
+    pub fn new(value: F) -> Self {
+        Self {
+            value,
+            max: None,
+            min: None,
+            last: None,
+        }
+    }
+
+    pub fn push(&mut self, point: T) {
+        let value = &self.value;
+        if self.max.is_none_or(|max| value(point) > value(max)) {
+            self.max = Some(point);
+        }
+
+        if self.min.is_none_or(|min| value(point) < value(min)) {
+            self.min = Some(point);
+        }
+
+        self.last = Some(point);
+    }
+
+    /// The points found, like [`extremes`]
+    pub fn into_points(self) -> Vec<T> {
+        let mut points: Vec<T> = vec![];
+        for point in [self.max, self.min, self.last].into_iter().flatten() {
+            if !points.contains(&point) {
+                points.push(point);
+            }
+        }
+
+        points
+    }
+}
+
+/// Marks for the extremes of a column graph's values, each given by its index along the x axis
+///
+/// Each is labelled with its value, in `unit` if there is one. The row each is drawn in is
+/// estimated from the range and how many steps each character is divided into, then moved to the
+/// nearest character that's drawn in that column.
+pub fn column_marks(
+    lines: &[&str],
+    points: &[(usize, f64)],
+    values_per_column: usize,
+    steps_per_row: usize,
+    (minimum, maximum): (f64, f64),
+    unit: Option<Unit>,
+) -> Vec<Mark> {
+    let grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    let height = grid.len();
+    let steps = height * steps_per_row;
+
+    extremes(points.iter().copied(), |(_, value)| value)
+        .into_iter()
+        .filter(|_| height > 0)
+        .map(|(index, value)| {
+            let column = index / values_per_column;
+
+            let span = maximum - minimum;
+            let fraction = if span > 0. {
+                (value - minimum) / span
+            } else {
+                0.
+            };
+            #[allow(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                clippy::cast_precision_loss
+            )]
+            let step = (fraction.clamp(0., 1.) * (steps - 1) as f64).round() as usize;
+            let estimate = height - 1 - step / steps_per_row;
+
+            let is_drawn = |row: usize| grid[row].get(column).is_some_and(|c| !is_empty(*c));
+            let row = (0..height)
+                .filter(|row| is_drawn(*row))
+                .min_by_key(|row| row.abs_diff(estimate))
+                .unwrap_or(estimate);
+
+            Mark {
+                row,
+                column,
+                label: units::label(value, unit),
+            }
+        })
+        .collect()
+}
+
+/// Whether a character has nothing drawn in it
+fn is_empty(c: char) -> bool {
+    c == ' ' || c == '\u{2800}'
+}
+
+/// Write each mark's label into the empty space next to it, keeping within `width` characters
+///
+/// Labels are tried above the point, then beside it, then below it, and are skipped if none of
+/// those have room. Labels on the same line are kept at least a space apart.
+pub fn label(lines: &[&str], marks: &[Mark], width: usize) -> Vec<String> {
+    let mut grid: Vec<Vec<char>> = lines.iter().map(|line| line.chars().collect()).collect();
+    let mut labelled: Vec<(usize, std::ops::Range<usize>)> = vec![];
+
+    for mark in marks {
+        let length = mark.label.chars().count() + 1;
+        let Mark { row, column, .. } = *mark;
+
+        // Each place the label could go, as the row, the column it starts at, and the arrow
+        // (which is next to the point) along with whether it goes before the value
+        let mut places = vec![];
+        if let Some(above) = row.checked_sub(1) {
+            places.push((above, Some(column), '↓', true));
+            places.push((above, (column + 1).checked_sub(length), '↓', false));
+        }
+        places.push((row, Some(column + 1), '←', true));
+        places.push((row, column.checked_sub(length), '→', false));
+        places.push((row + 1, Some(column), '↑', true));
+        places.push((row + 1, (column + 1).checked_sub(length), '↑', false));
+
+        let place = places
+            .into_iter()
+            .find_map(|(row, start, arrow, is_first)| {
+                let start = start?;
+                let line = grid.get(row)?;
+                let fits = start + length <= width
+                    && (start..start + length).all(|i| line.get(i).is_none_or(|c| is_empty(*c)))
+                    && !labelled.iter().any(|(labelled_row, columns)| {
+                        *labelled_row == row
+                            && columns.start <= start + length
+                            && start <= columns.end
+                    });
+                fits.then_some((row, start, arrow, is_first))
+            });
+
+        let Some((row, start, arrow, is_first)) = place else {
+            continue;
+        };
+
+        let text: Vec<char> = if is_first {
+            std::iter::once(arrow).chain(mark.label.chars()).collect()
+        } else {
+            mark.label.chars().chain(std::iter::once(arrow)).collect()
+        };
+
+        let line = &mut grid[row];
+        if line.len() < start + length {
+            line.resize(start + length, ' ');
+        }
+        line[start..start + length].copy_from_slice(&text);
+        labelled.push((row, start..start + length));
+    }
+
+    grid.into_iter().map(String::from_iter).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_extremes() {
+        let points = [(0, 3.), (1, 1.), (2, 8.), (3, 8.), (4, 1.)];
+        assert_eq!(
+            vec![(2, 8.), (1, 1.), (4, 1.)],
+            extremes(points, |(_, value)| value)
+        );
+
+        // The last point is also the largest
+        assert_eq!(
+            vec![(1, 2.), (0, 1.)],
+            extremes([(0, 1.), (1, 2.)], |(_, value)| value)
+        );
+        assert!(extremes([], |(_, value): (usize, f64)| value).is_empty());
+    }
+
+    #[test]
+    fn label_empty_space() {
+        let lines = ["⠀⠀⠀⠀", "⠀⣿⠀⠀", "⣿⣿⣿⠀"];
+        let marks = [
+            Mark {
+                row: 1,
+                column: 1,
+                label: String::from("9"),
+            },
+            Mark {
+                row: 2,
+                column: 2,
+                label: String::from("42"),
+            },
+            Mark {
+                row: 2,
+                column: 0,
+                label: String::from("1"),
+            },
+        ];
+
+        // There's no room above the last point, or on either side, or below
+        assert_eq!(vec!["⠀↓9⠀", "⠀⣿↓42", "⣿⣿⣿⠀"], label(&lines, &marks, 5));
+    }
+
+    #[test]
+    fn label_before_arrow_at_edge() {
+        let lines = ["    ", "   █"];
+        let marks = [Mark {
+            row: 1,
+            column: 3,
+            label: String::from("7"),
+        }];
+
+        assert_eq!(vec!["  7↓", "   █"], label(&lines, &marks, 4));
+    }
+}
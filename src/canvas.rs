@@ -0,0 +1,500 @@
+//! A canvas of dots to draw anything on, printed with the same characters as the graphs.
+//!
+//! Dots are counted from the top left corner, with `x` going right and `y` going down. Anything
+//! drawn outside the canvas is cut off.
+//!
+//! # Example
+//!
+//! ```
+//! use braille::canvas::{Canvas, Glyphs};
+//!
+//! let mut canvas = Canvas::try_new(8, 8)?;
+//! canvas.rect(0, 0, 8, 8);
+//! canvas.line(0, 0, 7, 7);
+//!
+//! assert_eq!(
+//!     canvas.render(Glyphs::Braille),
+//!     "⡟⢍⠉⢹\n⣇⣀⣑⣼\n",
+//! );
+//! # Ok::<(), anyhow::Error>(())
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::graph::braille::Braille;
+use crate::graph::glyphs::{self, GlyphSet};
+use crate::graph::mini_blocks::Quadrants;
+use crate::graph::octants::Octants;
+use crate::graph::sextants::Sextants;
+
+/// The characters a canvas is printed with, each showing a block of dots
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Glyphs {
+    /// Braille patterns, 2 dots wide and 4 tall
+    #[default]
+    Braille,
+
+    /// Octants, 2 dots wide and 4 tall
+    Octants,
+
+    /// Sextants, 2 dots wide and 3 tall
+    Sextants,
+
+    /// Quadrants, 2 dots wide and 2 tall
+    Quadrants,
+}
+
+impl Glyphs {
+    /// How many dots wide and tall each character is
+    #[must_use]
+    pub fn cell_size(self) -> (usize, usize) {
+        match self {
+            Self::Braille => glyphs::cell_size::<Braille, 4>(),
+            Self::Octants => glyphs::cell_size::<Octants, 4>(),
+            Self::Sextants => glyphs::cell_size::<Sextants, 3>(),
+            Self::Quadrants => glyphs::cell_size::<Quadrants, 2>(),
+        }
+    }
+}
+
+/// A grid of dots, which are either set or not
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    width: usize,
+    height: usize,
+    /// One bit for each dot, a row at a time from the top left
+    dots: Vec<u64>,
+    /// Text written over the dots, from the dot each piece starts at
+    text: Vec<(i32, i32, String)>,
+}
+
+impl Canvas {
+    /// An empty canvas, `width` dots wide and `height` dots tall, unless there are too many dots
+    /// to count
+    pub fn try_new(width: usize, height: usize) -> anyhow::Result<Self> {
+        let Some(dots) = width.checked_mul(height) else {
+            anyhow::bail!("A canvas {width} dots wide and {height} dots tall is too large");
+        };
+
+        Ok(Self {
+            width,
+            height,
+            dots: vec![0; dots.div_ceil(64)],
+            text: vec![],
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The index of a dot, if it's on the canvas
+    fn index(&self, x: i64, y: i64) -> Option<usize> {
+        let x = usize::try_from(x).ok().filter(|x| *x < self.width)?;
+        let y = usize::try_from(y).ok().filter(|y| *y < self.height)?;
+        Some(y * self.width + x)
+    }
+
+    fn is_set(&self, index: usize) -> bool {
+        self.dots[index / 64] & 1 << (index % 64) != 0
+    }
+
+    fn insert(&mut self, index: usize) {
+        self.dots[index / 64] |= 1 << (index % 64);
+    }
+
+    /// Whether a dot is set (dots off the canvas never are)
+    #[must_use]
+    pub fn get(&self, x: i32, y: i32) -> bool {
+        self.index(x.into(), y.into())
+            .is_some_and(|index| self.is_set(index))
+    }
+
+    /// Set a single dot
+    pub fn set(&mut self, x: i32, y: i32) {
+        self.set_wide(x.into(), y.into());
+    }
+
+    /// Set a single dot, which may be further from the canvas than an `i32` reaches
+    fn set_wide(&mut self, x: i64, y: i64) {
+        if let Some(index) = self.index(x, y) {
+            self.insert(index);
+        }
+    }
+
+    /// Draw a straight line between two dots, including both ends
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32) {
+        self.line_wide((x0.into(), y0.into()), (x1.into(), y1.into()));
+    }
+
+    /// Draw a straight line between two dots, only stepping through the part on the canvas
+    fn line_wide(&mut self, start: (i64, i64), end: (i64, i64)) {
+        let Some(((x0, y0), (x1, y1))) = self.clip(start, end) else {
+            return;
+        };
+
+        // Bresenham's line algorithm, which works in every direction
+        let dx = (x1 - x0).abs();
+        let dy = -(y1 - y0).abs();
+        let step_x = if x0 < x1 { 1 } else { -1 };
+        let step_y = if y0 < y1 { 1 } else { -1 };
+
+        let (mut x, mut y) = (x0, y0);
+        let mut error = dx + dy;
+        loop {
+            self.set_wide(x, y);
+            if x == x1 && y == y1 {
+                break;
+            }
+
+            let doubled = 2 * error;
+            if doubled >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if doubled <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    /// Cut a line down to the part that's on the canvas, if any of it is
+    ///
+    /// Lines that are entirely on the canvas are left as they are, and the ends of the rest are
+    /// moved along them to the nearest dot on the edges they cross (with the Liang-Barsky
+    /// algorithm).
+    #[allow(clippy::cast_precision_loss, clippy::cast_possible_truncation)]
+    fn clip(&self, (x0, y0): (i64, i64), (x1, y1): (i64, i64)) -> Option<((i64, i64), (i64, i64))> {
+        let right = i64::try_from(self.width).ok()?.checked_sub(1)?;
+        let bottom = i64::try_from(self.height).ok()?.checked_sub(1)?;
+        let is_inside = |x, y| (0..=right).contains(&x) && (0..=bottom).contains(&y);
+        if is_inside(x0, y0) && is_inside(x1, y1) {
+            return Some(((x0, y0), (x1, y1)));
+        }
+
+        let (x0, y0, x1, y1) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64);
+        let (dx, dy) = (x1 - x0, y1 - y0);
+
+        // How far along the line it enters and leaves the canvas, from 0 at its start to 1 at its
+        // end
+        let (mut enters, mut leaves) = (0_f64, 1_f64);
+        for (towards_edge, to_edge) in [
+            (-dx, x0),
+            (dx, right as f64 - x0),
+            (-dy, y0),
+            (dy, bottom as f64 - y0),
+        ] {
+            if towards_edge == 0. {
+                // Parallel to this edge, so it's either always inside it or never
+                if to_edge < 0. {
+                    return None;
+                }
+            } else if towards_edge < 0. {
+                enters = enters.max(to_edge / towards_edge);
+            } else {
+                leaves = leaves.min(to_edge / towards_edge);
+            }
+        }
+
+        if enters > leaves {
+            return None;
+        }
+
+        let at = |t: f64| ((x0 + t * dx).round() as i64, (y0 + t * dy).round() as i64);
+        Some((at(enters), at(leaves)))
+    }
+
+    /// Draw lines joining each dot to the next
+    pub fn polyline(&mut self, points: &[(i32, i32)]) {
+        if let [(x, y)] = points {
+            self.set(*x, *y);
+        }
+
+        for pair in points.windows(2) {
+            let [(x0, y0), (x1, y1)] = pair else {
+                unreachable!("windows should have two points");
+            };
+            self.line(*x0, *y0, *x1, *y1);
+        }
+    }
+
+    /// Draw the outline of a rectangle `width` dots wide and `height` dots tall, from its top left
+    /// corner
+    pub fn rect(&mut self, x: i32, y: i32, width: usize, height: usize) {
+        let (Ok(width), Ok(height)) = (i64::try_from(width), i64::try_from(height)) else {
+            return;
+        };
+        if width == 0 || height == 0 {
+            return;
+        }
+
+        // Edges past the canvas are moved to just past it, which leaves the same part on it
+        let (left, top) = (i64::from(x), i64::from(y));
+        let past = |length: usize| i64::try_from(length).unwrap_or(i64::MAX);
+        let right = left.saturating_add(width - 1).min(past(self.width));
+        let bottom = top.saturating_add(height - 1).min(past(self.height));
+        let corners = [(left, top), (right, top), (right, bottom), (left, bottom)];
+        for (index, corner) in corners.iter().enumerate() {
+            self.line_wide(*corner, corners[(index + 1) % corners.len()]);
+        }
+    }
+
+    /// Draw the outline of a circle around a dot
+    pub fn circle(&mut self, x: i32, y: i32, radius: usize) {
+        let Ok(radius) = i64::try_from(radius) else {
+            return;
+        };
+        let (x, y) = (i64::from(x), i64::from(y));
+        if !self.is_on_circle(x, y, radius) {
+            return;
+        }
+
+        // The midpoint circle algorithm, drawing one eighth and mirroring it
+        let (mut dx, mut dy) = (radius, 0);
+        let mut error = 1 - radius;
+        while dx >= dy {
+            for (offset_x, offset_y) in [(dx, dy), (dy, dx)] {
+                self.set_wide(x + offset_x, y + offset_y);
+                self.set_wide(x - offset_x, y + offset_y);
+                self.set_wide(x + offset_x, y - offset_y);
+                self.set_wide(x - offset_x, y - offset_y);
+            }
+
+            dy += 1;
+            if error < 0 {
+                error += 2 * dy + 1;
+            } else {
+                dx -= 1;
+                error += 2 * (dy - dx) + 1;
+            }
+        }
+    }
+
+    /// Whether any of a circle's outline could be on the canvas, so it's worth drawing
+    ///
+    /// It isn't when the canvas is entirely outside the circle's bounding box, or entirely
+    /// inside the circle.
+    #[allow(clippy::cast_precision_loss)]
+    fn is_on_circle(&self, x: i64, y: i64, radius: i64) -> bool {
+        let (x, y, radius) = (x as f64, y as f64, radius as f64);
+        let (right, bottom) = (self.width as f64 - 1., self.height as f64 - 1.);
+        if right < 0. || bottom < 0. {
+            return false;
+        }
+
+        let is_near =
+            x + radius >= 0. && x - radius <= right && y + radius >= 0. && y - radius <= bottom;
+        let is_inside = [(0., 0.), (right, 0.), (0., bottom), (right, bottom)]
+            .iter()
+            .all(|(corner_x, corner_y)| (corner_x - x).hypot(corner_y - y) < radius - 1.);
+
+        is_near && !is_inside
+    }
+
+    /// Set every dot that can be reached from this one without crossing a dot that's already set
+    ///
+    /// Nothing is filled if the dot is already set, or off the canvas.
+    pub fn fill(&mut self, x: i32, y: i32) {
+        let mut stack = vec![(i64::from(x), i64::from(y))];
+        while let Some((x, y)) = stack.pop() {
+            let Some(index) = self.index(x, y) else {
+                continue;
+            };
+            if self.is_set(index) {
+                continue;
+            }
+
+            self.insert(index);
+            stack.extend([(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]);
+        }
+    }
+
+    /// Write text over the canvas, starting at the character that shows this dot
+    ///
+    /// Each character of the text takes the place of the dots in one character of the canvas.
+    /// Characters that fall outside the canvas are cut off, and the rest are still written.
+    pub fn text(&mut self, x: i32, y: i32, text: &str) {
+        self.text.push((x, y, text.to_string()));
+    }
+
+    /// The character showing the dots of one character of the canvas
+    fn glyph<G: GlyphSet<ROWS>, const ROWS: usize>(&self, row: usize, column: usize) -> char {
+        let mut cell = [[false; glyphs::WIDTH]; ROWS];
+        for (y, cell_row) in cell.iter_mut().enumerate() {
+            for (x, dot) in cell_row.iter_mut().enumerate() {
+                let x = i64::try_from(column * glyphs::WIDTH + x).unwrap_or(i64::MAX);
+                let y = i64::try_from(row * ROWS + y).unwrap_or(i64::MAX);
+                *dot = self.index(x, y).is_some_and(|index| self.is_set(index));
+            }
+        }
+        G::glyph(cell)
+    }
+
+    /// Print the canvas with a set of characters, with a newline after each row
+    #[must_use]
+    pub fn render(&self, glyphs: Glyphs) -> String {
+        let (cell_width, cell_height) = glyphs.cell_size();
+        let columns = self.width.div_ceil(cell_width);
+        let rows = self.height.div_ceil(cell_height);
+
+        // The character of text to write in place of each character of dots, leaving out the
+        // characters before or past the edges
+        let mut text = HashMap::new();
+        for (x, y, line) in &self.text {
+            let row = i64::from(*y).div_euclid(i64::try_from(cell_height).unwrap_or(i64::MAX));
+            let Some(row) = usize::try_from(row).ok().filter(|row| *row < rows) else {
+                continue;
+            };
+
+            let first = i64::from(*x).div_euclid(i64::try_from(cell_width).unwrap_or(i64::MAX));
+            for (column, c) in (first..).zip(line.chars()) {
+                if let Some(column) = usize::try_from(column).ok().filter(|c| *c < columns) {
+                    text.insert((row, column), c);
+                }
+            }
+        }
+
+        let mut output = String::with_capacity(rows * (columns + 1) * 3);
+        for row in 0..rows {
+            for column in 0..columns {
+                if let Some(c) = text.get(&(row, column)) {
+                    output.push(*c);
+                    continue;
+                }
+
+                output.push(match glyphs {
+                    Glyphs::Braille => self.glyph::<Braille, 4>(row, column),
+                    Glyphs::Octants => self.glyph::<Octants, 4>(row, column),
+                    Glyphs::Sextants => self.glyph::<Sextants, 3>(row, column),
+                    Glyphs::Quadrants => self.glyph::<Quadrants, 2>(row, column),
+                });
+            }
+            output.push('\n');
+        }
+
+        output
+    }
+}
+
+/// Print the canvas in braille
+impl fmt::Display for Canvas {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(Glyphs::Braille))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each dot of the canvas, as `#` if it's set, or `.` if not
+    fn dots(canvas: &Canvas) -> String {
+        (0..canvas.height)
+            .map(|y| {
+                (0..canvas.width)
+                    .map(|x| {
+                        let index = y * canvas.width + x;
+                        if canvas.is_set(index) { '#' } else { '.' }
+                    })
+                    .collect()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn lines_in_every_direction() {
+        let mut canvas = Canvas::try_new(5, 5).unwrap();
+        canvas.line(0, 0, 4, 2);
+        canvas.line(4, 4, 0, 4);
+        canvas.line(0, 4, 0, 2);
+        assert_eq!(dots(&canvas), "#....\n.##..\n#..##\n#....\n#####");
+    }
+
+    #[test]
+    fn shapes_are_cut_off_at_the_edges() {
+        let mut canvas = Canvas::try_new(4, 4).unwrap();
+        canvas.rect(-2, 1, 4, 2);
+        canvas.circle(4, 0, 1);
+        canvas.set(10, 10);
+        assert_eq!(dots(&canvas), "...#\n##..\n##..\n....");
+    }
+
+    #[test]
+    fn shapes_far_off_the_canvas() {
+        let mut canvas = Canvas::try_new(4, 4).unwrap();
+        canvas.line(i32::MIN, i32::MIN, i32::MAX, i32::MAX);
+        canvas.rect(i32::MAX, i32::MAX, usize::MAX, usize::MAX);
+        canvas.rect(-1, -1, usize::MAX / 2, 3);
+        canvas.circle(i32::MIN, 0, usize::MAX);
+        canvas.circle(0, 0, usize::MAX >> 2);
+        assert_eq!(dots(&canvas), "#...\n####\n..#.\n...#");
+    }
+
+    #[test]
+    fn lines_are_clipped_to_the_canvas() {
+        let mut canvas = Canvas::try_new(5, 3).unwrap();
+        canvas.line(-4, 0, 8, 0);
+        canvas.line(-1, 3, 5, 0);
+        canvas.line(2, -100, 2, -1);
+        assert_eq!(dots(&canvas), "#####\n...##\n.##..");
+    }
+
+    #[test]
+    fn circle_and_fill() {
+        let mut canvas = Canvas::try_new(7, 7).unwrap();
+        canvas.circle(3, 3, 3);
+        canvas.fill(3, 3);
+        assert_eq!(
+            dots(&canvas),
+            "..###..\n.#####.\n#######\n#######\n#######\n.#####.\n..###.."
+        );
+
+        // Filling from a dot that's set does nothing
+        let mut canvas = Canvas::try_new(3, 3).unwrap();
+        canvas.set(1, 1);
+        canvas.fill(1, 1);
+        assert_eq!(dots(&canvas), "...\n.#.\n...");
+    }
+
+    #[test]
+    fn render_glyphs() {
+        let mut canvas = Canvas::try_new(4, 4).unwrap();
+        canvas.rect(0, 0, 4, 4);
+
+        assert_eq!(canvas.render(Glyphs::Braille), "⣏⣹\n");
+        assert_eq!(canvas.render(Glyphs::Quadrants), "▛▜\n▙▟\n");
+        assert_eq!(canvas.render(Glyphs::Sextants), "🬕🬨\n🬂🬂\n");
+        assert_eq!(canvas.to_string(), canvas.render(Glyphs::Braille));
+    }
+
+    #[test]
+    fn too_many_dots() {
+        assert!(Canvas::try_new(usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn text_cut_off_at_the_edges() {
+        let mut canvas = Canvas::try_new(8, 4).unwrap();
+        canvas.text(-4, 0, "hello");
+        canvas.text(5, 2, "world");
+        canvas.text(0, -2, "above");
+        assert_eq!(canvas.render(Glyphs::Quadrants), "llo \n  wo\n");
+    }
+
+    #[test]
+    fn text_over_dots() {
+        let mut canvas = Canvas::try_new(8, 4).unwrap();
+        canvas.polyline(&[(0, 3), (7, 3)]);
+        canvas.text(2, 1, "hello");
+        assert_eq!(canvas.render(Glyphs::Quadrants), " hel\n▄▄▄▄\n");
+    }
+}
@@ -0,0 +1,450 @@
+//! Defaults and presets read from config files.
+//!
+//! Config files are TOML, where each key is the name of a long option (or the name of its value,
+//! like `size`), and the values are what would be passed on the command line. Presets are tables
+//! under `preset`, and are chosen with `--preset`:
+//!
+//! ```toml
+//! # Used every time
+//! style = "filled"
+//!
+//! [preset.latency]
+//! kind = "braille-columns"
+//! range = "0:"
+//! hline = ["250:SLO", "500"]
+//! ```
+//!
+//! Settings are applied in this order, with later ones taking precedence:
+//!
+//! 1. The user config file (`$XDG_CONFIG_HOME/braille/config.toml`, or `--config`)
+//! 2. The nearest `.braille.toml` in the current directory or any of its parents
+//! 3. The preset from the user config file
+//! 4. The preset from `.braille.toml`
+//! 5. The `BRAILLE_PER`, `BRAILLE_STYLE`, and `BRAILLE_USE_FULL_DEFAULT_HEIGHT` environment
+//!    variables
+//! 6. The command line
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+use clap::parser::{ArgMatches, ValueSource};
+use clap::{Arg, ArgGroup, Command};
+use toml::{Table, Value};
+
+const PROJECT_FILE_NAME: &str = ".braille.toml";
+
+/// A config file that's been read
+#[derive(Debug, Clone)]
+pub struct ConfigFile {
+    path: PathBuf,
+    table: Table,
+}
+
+impl ConfigFile {
+    /// Read a config file, if it exists
+    pub fn try_read(path: &Path) -> anyhow::Result<Option<Self>> {
+        let contents = match std::fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => {
+                return Err(err).with_context(|| format!("Failed to read {}", path.display()));
+            }
+        };
+
+        Self::parse(path, &contents).map(Some)
+    }
+
+    fn parse(path: &Path, contents: &str) -> anyhow::Result<Self> {
+        let table = contents
+            .parse::<Table>()
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+
+        match table.get("preset") {
+            None => {}
+            Some(Value::Table(presets)) => {
+                for (name, preset) in presets {
+                    anyhow::ensure!(
+                        preset.is_table(),
+                        "Preset {name:?} in {} should be a table",
+                        path.display()
+                    );
+                }
+            }
+            Some(_) => anyhow::bail!("\"preset\" in {} should be a table", path.display()),
+        }
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            table,
+        })
+    }
+
+    /// Read the user config file (or the one given) and the nearest project config file
+    pub fn read_all(user_path: Option<&Path>) -> anyhow::Result<Vec<Self>> {
+        let user_path = match user_path {
+            Some(path) => {
+                anyhow::ensure!(path.exists(), "Config file not found: {}", path.display());
+                Some(path.to_path_buf())
+            }
+            None => Self::user_path(),
+        };
+
+        let project_path = std::env::current_dir()
+            .ok()
+            .and_then(|dir| Self::find_project_path(&dir));
+
+        let mut files = vec![];
+        for path in user_path.iter().chain(&project_path) {
+            if let Some(file) = Self::try_read(path)? {
+                files.push(file);
+            }
+        }
+
+        Ok(files)
+    }
+
+    fn user_path() -> Option<PathBuf> {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+            .map(|dir| dir.join("braille").join("config.toml"))
+    }
+
+    fn find_project_path(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(PROJECT_FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    /// The top level settings, without any presets
+    fn defaults(&self) -> impl Iterator<Item = (&String, &Value)> {
+        self.table.iter().filter(|(key, _)| *key != "preset")
+    }
+
+    /// The settings for a preset, if this file has it
+    fn preset(&self, name: &str) -> Option<&Table> {
+        self.table
+            .get("preset")
+            .and_then(|presets| presets.get(name))
+            .and_then(Value::as_table)
+    }
+}
+
+/// Turn the settings from each config file into arguments, leaving out anything already given
+/// on the command line or by an environment variable
+///
+/// The settings from each file are checked on their own first, so mistakes are reported with the
+/// file they came from. The command should already be built, so its groups are known.
+pub fn to_args(
+    cmd: &Command,
+    files: &[ConfigFile],
+    preset: Option<&str>,
+    matches: &ArgMatches,
+) -> anyhow::Result<Vec<OsString>> {
+    let mut layers = vec![];
+    for file in files {
+        layers.push((&file.path, file.defaults().collect::<Vec<_>>()));
+    }
+
+    if let Some(name) = preset {
+        let mut found = false;
+        for file in files {
+            if let Some(preset) = file.preset(name) {
+                found = true;
+                layers.push((&file.path, preset.iter().collect()));
+            }
+        }
+
+        anyhow::ensure!(found, "Preset not found: {name}");
+    }
+
+    // Later layers replace the settings of earlier ones
+    let mut settings: Vec<(&str, Vec<OsString>)> = vec![];
+    for (path, layer) in layers {
+        let mut layer_args = vec![];
+        for (key, value) in layer {
+            let args = setting_to_args(cmd, key, value)
+                .with_context(|| format!("Invalid config file {}", path.display()))?;
+            layer_args.extend(args.iter().cloned());
+
+            settings.retain(|(existing, _)| existing != key);
+            settings.push((key, args));
+        }
+
+        cmd.clone()
+            .no_binary_name(true)
+            .try_get_matches_from(layer_args)
+            .with_context(|| format!("Invalid config file {}", path.display()))?;
+    }
+
+    let mut options = vec![];
+    let mut positionals = vec![];
+    for (key, args) in settings {
+        let arg = find_arg(cmd, key).expect("setting should already be validated");
+        if !is_overridden(cmd, arg, matches) {
+            if arg.is_positional() {
+                positionals.extend(args);
+            } else {
+                options.extend(args);
+            }
+        }
+    }
+
+    options.extend(positionals);
+    Ok(options)
+}
+
+fn find_arg<'a>(cmd: &'a Command, key: &str) -> Option<&'a Arg> {
+    cmd.get_arguments()
+        .find(|arg| arg.get_long() == Some(key) || arg.get_id().as_str() == key.replace('-', "_"))
+}
+
+/// Whether the argument (or anything it can't be used with) was given on the command line or by
+/// an environment variable
+fn is_overridden(cmd: &Command, arg: &Arg, matches: &ArgMatches) -> bool {
+    let is_explicit = |id: &str| {
+        matches!(
+            matches.value_source(id),
+            Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+        )
+    };
+
+    // Groups that allow more than one argument (like the one for every argument) don't matter
+    let in_same_group = cmd
+        .get_groups()
+        .filter(|group| !ArgGroup::clone(group).is_multiple())
+        .any(|group| {
+            group.get_args().any(|id| id == arg.get_id())
+                && group.get_args().any(|id| is_explicit(id.as_str()))
+        });
+
+    // Conflicts are only declared on one of the two arguments
+    let conflicts = |a: &Arg, b: &Arg| {
+        cmd.get_arg_conflicts_with(a)
+            .into_iter()
+            .any(|other| other.get_id() == b.get_id())
+    };
+
+    is_explicit(arg.get_id().as_str())
+        || in_same_group
+        || cmd.get_arguments().any(|other| {
+            is_explicit(other.get_id().as_str()) && (conflicts(arg, other) || conflicts(other, arg))
+        })
+}
+
+/// Turn a single setting into the arguments that would be passed on the command line
+fn setting_to_args(cmd: &Command, key: &str, value: &Value) -> anyhow::Result<Vec<OsString>> {
+    anyhow::ensure!(
+        !matches!(
+            key,
+            "config" | "preset" | "modeline" | "command" | "help" | "version"
+        ),
+        "{key:?} can't be set in a config file"
+    );
+
+    let arg = find_arg(cmd, key).with_context(|| format!("Unknown setting: {key:?}"))?;
+
+    let values = match value {
+        Value::Array(values) => values.iter().map(value_to_string).collect(),
+        value => value_to_string(value).map(|value| vec![value]),
+    }
+    .with_context(|| format!("Invalid value for {key:?}"))?;
+
+    if !arg.get_action().takes_values() {
+        return match value {
+            Value::Boolean(true) => Ok(vec![flag(arg)]),
+            Value::Boolean(false) => Ok(vec![]),
+            _ => anyhow::bail!("{key:?} should be true or false"),
+        };
+    }
+
+    if arg.is_positional() {
+        return Ok(values.into_iter().map(OsString::from).collect());
+    }
+
+    let takes_many = arg
+        .get_num_args()
+        .is_some_and(|range| range.max_values() > 1);
+
+    let mut args = vec![];
+    if takes_many {
+        args.push(flag(arg));
+        args.extend(values.into_iter().map(OsString::from));
+    } else {
+        for value in values {
+            args.push(flag(arg));
+            args.push(OsString::from(value));
+        }
+    }
+
+    Ok(args)
+}
+
+fn flag(arg: &Arg) -> OsString {
+    match (arg.get_long(), arg.get_short()) {
+        (Some(long), _) => OsString::from(format!("--{long}")),
+        (None, Some(short)) => OsString::from(format!("-{short}")),
+        (None, None) => unreachable!("positional arguments don't have a flag"),
+    }
+}
+
+fn value_to_string(value: &Value) -> anyhow::Result<String> {
+    match value {
+        Value::String(value) => Ok(value.clone()),
+        Value::Integer(value) => Ok(value.to_string()),
+        Value::Float(value) => Ok(value.to_string()),
+        Value::Boolean(value) => Ok(value.to_string()),
+        Value::Datetime(_) | Value::Array(_) | Value::Table(_) => {
+            anyhow::bail!("Expected a string, number, or boolean")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Opt;
+    use clap::CommandFactory;
+
+    fn config_file(contents: &str) -> ConfigFile {
+        ConfigFile::parse(Path::new("config.toml"), contents).unwrap()
+    }
+
+    fn command() -> Command {
+        let mut cmd = Opt::command();
+        cmd.build();
+        cmd
+    }
+
+    fn get_args(files: &[ConfigFile], preset: Option<&str>, cli: &[&str]) -> Vec<String> {
+        let cmd = command();
+        let matches = cmd.clone().get_matches_from(cli);
+        to_args(&cmd, files, preset, &matches)
+            .unwrap()
+            .into_iter()
+            .map(|arg| arg.into_string().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn settings_to_args() {
+        let file = config_file(
+            r#"
+            size = 10
+            kind = "columns"
+            use-full-default-height = true
+            stack = false
+            hline = [1, "2.5:two"]
+            "#,
+        );
+
+        assert_eq!(
+            vec![
+                "--hline",
+                "1",
+                "--hline",
+                "2.5:two",
+                "--kind",
+                "columns",
+                "--use-full-default-height",
+                "10"
+            ],
+            get_args(&[file], None, &["braille"])
+        );
+    }
+
+    #[test]
+    fn preset_overrides_defaults() {
+        let user = config_file(
+            r#"
+            style = "line"
+            range = "0:"
+
+            [preset.latency]
+            range = "0:500"
+            "#,
+        );
+        let project = config_file(
+            r#"
+            [preset.latency]
+            style = "filled"
+            "#,
+        );
+
+        assert_eq!(
+            vec!["--range", "0:500", "--style", "filled"],
+            get_args(&[user, project], Some("latency"), &["braille"])
+        );
+    }
+
+    #[test]
+    fn command_line_overrides_settings() {
+        let file = config_file(
+            r#"
+            kind = "columns"
+            range = "0:"
+            style = "line"
+            size = 10
+            "#,
+        );
+
+        // `-c` is in the same group as `--kind`
+        assert_eq!(
+            vec!["--range", "0:", "--style", "line"],
+            get_args(&[file], None, &["braille", "-c", "5"])
+        );
+    }
+
+    #[test]
+    fn conflicts_with_command_line() {
+        let file = config_file(
+            r#"
+            kind = "columns"
+            range = "0:"
+            style = "line"
+            "#,
+        );
+
+        // `--modeline` can't be used with `--kind` or `--range`
+        assert_eq!(
+            vec!["--style", "line"],
+            get_args(&[file], None, &["braille", "--modeline"])
+        );
+    }
+
+    #[test]
+    fn missing_preset() {
+        let cmd = command();
+        let matches = cmd.clone().get_matches_from(["braille"]);
+        let file = config_file("kind = \"columns\"");
+        assert!(to_args(&cmd, &[file], Some("latency"), &matches).is_err());
+    }
+
+    #[test]
+    fn invalid_settings() {
+        let cmd = command();
+        let matches = cmd.clone().get_matches_from(["braille"]);
+
+        for contents in [
+            "unknown = 1",
+            "kind = \"pie\"",
+            "stack = \"yes\"",
+            "config = \"other.toml\"",
+        ] {
+            let file = config_file(contents);
+            assert!(
+                to_args(&cmd, &[file], None, &matches).is_err(),
+                "{contents} should be invalid"
+            );
+        }
+
+        for contents in ["preset = 1", "[preset]\nlatency = 1"] {
+            assert!(
+                ConfigFile::parse(Path::new("config.toml"), contents).is_err(),
+                "{contents} should be invalid"
+            );
+        }
+    }
+}
@@ -1,6 +1,7 @@
 pub mod blocks;
 pub mod braille;
 mod dot_plotter;
+pub mod glyphs;
 pub mod mini_blocks;
 pub mod octants;
 pub mod sextants;
@@ -9,7 +10,7 @@ use std::io::{LineWriter, Write};
 use std::str::FromStr;
 
 use crate::InputLine;
-use crate::opt::{Config, Configurable, GraphStyle, ValueIter};
+use crate::opt::{Config, Configurable, GraphStyle, ReferenceLine, ValueIter};
 
 pub trait Graphable<T, Conf: Configurable = Config>
 where
@@ -30,6 +31,21 @@ where
         self.config().style()
     }
 
+    fn stack(&self) -> bool {
+        self.config().stack()
+    }
+
+    fn baseline(&self) -> Option<f64> {
+        self.config().baseline()
+    }
+
+    fn references<'a>(&'a self) -> &'a [ReferenceLine]
+    where
+        Conf: 'a,
+    {
+        self.config().references()
+    }
+
     fn print_graph<W: Write>(
         &self,
         lines: ValueIter<T>,
@@ -184,10 +200,338 @@ pub trait DotArrayable {
     }
 }
 
+/// How many dots apart each series is filled when stacked, cycling for each series after that
+pub const STACK_STRIDES: [usize; 3] = [1, 2, 3];
+
+/// The shade each series fills its blocks with when stacked in the block kinds, cycling for each
+/// series after that
+pub const STACK_SHADES: [&str; 3] = ["\u{2588}", "\u{2592}", "\u{2591}"];
+
+pub trait Stackable {
+    /// Scale the running total at each value in a line, skipping over missing values
+    #[must_use]
+    fn scale_totals(
+        values: impl IntoIterator<Item = Option<f64>>,
+        scale: impl Fn(f64) -> u16,
+    ) -> Vec<Option<u16>> {
+        let mut total = 0.;
+        values
+            .into_iter()
+            .map(|value| {
+                value.map(|value| {
+                    total += value;
+                    scale(total)
+                })
+            })
+            .collect()
+    }
+
+    /// Turn the scaled running totals of a line into dot groups, with each series filling the
+    /// space between its total and the one before it
+    ///
+    /// The first series starts from `zero`. Each series uses its own stride from
+    /// [`STACK_STRIDES`] counting from its total, so the total itself is always drawn. Missing
+    /// values don't contribute anything to the stack.
+    #[must_use]
+    fn into_stacked_dot_groups<const M: usize>(
+        totals: &[Option<u16>],
+        zero: u16,
+    ) -> Vec<[bool; M]> {
+        let end = totals
+            .iter()
+            .flatten()
+            .copied()
+            .chain([zero])
+            .max()
+            .unwrap();
+
+        let mut iter = vec![false; usize::from(end)];
+
+        let mut previous = None;
+        for (series, total) in totals.iter().enumerate() {
+            let Some(total) = *total else {
+                continue;
+            };
+
+            let stride = STACK_STRIDES[series % STACK_STRIDES.len()];
+
+            // Only the first series includes where it started from
+            let (start, end) = match previous {
+                None => (zero.min(total), zero.max(total)),
+                Some(previous) if previous < total => (previous + 1, total),
+                Some(previous) if previous > total => (total, previous - 1),
+                Some(_) => continue,
+            };
+
+            for i in start..=end {
+                if usize::from(i.abs_diff(total)) % stride == 0 {
+                    iter[usize::from(i) - 1] = true;
+                }
+            }
+
+            previous = Some(total);
+        }
+
+        let chunks = iter.chunks_exact(M);
+        let mut tip = chunks.remainder().to_vec();
+        let mut row: Vec<[bool; M]> = chunks
+            .into_iter()
+            .map(|chunk| chunk.try_into().unwrap())
+            .collect();
+        if !tip.is_empty() {
+            tip.resize(M, false);
+            row.push(tip.try_into().unwrap());
+        }
+        row
+    }
+}
+
+/// A reference line scaled to the dot it's drawn at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScaledReference<'a> {
+    pub dot: u16,
+    pub label: Option<&'a str>,
+}
+
+pub trait ReferenceLinable {
+    #[must_use]
+    fn scale_references(
+        references: &[ReferenceLine],
+        scale: impl Fn(f64) -> u16,
+    ) -> Vec<ScaledReference<'_>> {
+        references
+            .iter()
+            .map(|reference| ScaledReference {
+                dot: scale(reference.value),
+                label: reference.label.as_deref(),
+            })
+            .collect()
+    }
+
+    /// Dots of a column graph's character that the reference lines pass through, where `row`
+    /// counts characters up from the bottom
+    ///
+    /// Only the first column of dots is drawn in each character, which gives the line its dashes.
+    #[must_use]
+    fn column_reference_dots<const N: usize, const M: usize>(
+        references: &[ScaledReference],
+        row: usize,
+    ) -> [[bool; N]; M] {
+        let mut dots = [[false; N]; M];
+        for reference in references {
+            let index = usize::from(reference.dot - 1);
+            if index / M == row {
+                dots[M - 1 - index % M][0] = true;
+            }
+        }
+
+        dots
+    }
+
+    /// Labels of the reference lines drawn in `row` of a column graph, to print after that row
+    #[must_use]
+    fn column_reference_labels<const M: usize>(
+        references: &[ScaledReference],
+        row: usize,
+    ) -> String {
+        references
+            .iter()
+            .filter(|reference| usize::from(reference.dot - 1) / M == row)
+            .filter_map(|reference| reference.label)
+            .fold(String::new(), |mut labels, label| {
+                labels.push(' ');
+                labels.push_str(label);
+                labels
+            })
+    }
+
+    /// Dots of a bar graph's character that the reference lines pass through, where the
+    /// character is in the `column` and made from the input `lines` given
+    ///
+    /// Only every other line of input is drawn, which gives the line its dashes.
+    #[must_use]
+    fn bar_reference_dots<const N: usize, const M: usize>(
+        references: &[ScaledReference],
+        column: usize,
+        lines: std::ops::Range<usize>,
+    ) -> [[bool; N]; M] {
+        let mut dots = [[false; N]; M];
+        for reference in references {
+            let index = usize::from(reference.dot - 1);
+            if index / N == column {
+                for (row, line) in dots.iter_mut().zip(lines.clone()) {
+                    row[index % N] = line % 2 == 0;
+                }
+            }
+        }
+
+        dots
+    }
+
+    /// How many characters wide a bar graph needs to be to fit every reference line
+    #[must_use]
+    fn bar_reference_width<const N: usize>(references: &[ScaledReference]) -> usize {
+        references
+            .iter()
+            .map(|reference| usize::from(reference.dot).div_ceil(N))
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// A line with each label placed above where its reference line is drawn in a bar graph, if
+    /// there are any labels
+    ///
+    /// Labels that would overlap are pushed to the right.
+    #[must_use]
+    fn bar_reference_header<const N: usize>(references: &[ScaledReference]) -> Option<String> {
+        let mut labels: Vec<_> = references
+            .iter()
+            .filter_map(|reference| {
+                reference
+                    .label
+                    .map(|label| (usize::from(reference.dot - 1) / N, label))
+            })
+            .collect();
+
+        if labels.is_empty() {
+            return None;
+        }
+
+        labels.sort_by_key(|(column, _)| *column);
+
+        let mut header = String::new();
+        let mut width = 0;
+        for (column, label) in labels {
+            if width > 0 && column <= width {
+                header.push(' ');
+                width += 1;
+            }
+
+            header.push_str(&" ".repeat(column.saturating_sub(width)));
+            width = width.max(column);
+
+            header.push_str(label);
+            width += label.chars().count();
+        }
+
+        Some(header)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn stacked_dot_groups() {
+        struct Foo;
+        impl Stackable for Foo {}
+
+        // 1st series fills 1..=3, 2nd fills every other dot of 4..=7, 3rd every third of 8..=10
+        #[rustfmt::skip]
+        let expected = vec![
+            [ true,  true,  true, false],
+            [ true, false,  true, false],
+            [false,  true, false, false],
+        ];
+
+        let actual = Foo::into_stacked_dot_groups::<4>(&[Some(3), Some(7), Some(10)], 1);
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn column_reference_dots() {
+        struct Foo;
+        impl ReferenceLinable for Foo {}
+
+        let references = [
+            ScaledReference {
+                dot: 2,
+                label: None,
+            },
+            ScaledReference {
+                dot: 8,
+                label: None,
+            },
+        ];
+
+        #[rustfmt::skip]
+        let expected = [
+            [false, false],
+            [false, false],
+            [ true, false],
+            [false, false],
+        ];
+
+        assert_eq!(expected, Foo::column_reference_dots::<2, 4>(&references, 0));
+        assert_eq!(
+            [
+                [true, false],
+                [false, false],
+                [false, false],
+                [false, false]
+            ],
+            Foo::column_reference_dots::<2, 4>(&references, 1)
+        );
+    }
+
+    #[test]
+    fn bar_reference_dots() {
+        struct Foo;
+        impl ReferenceLinable for Foo {}
+
+        let references = [ScaledReference {
+            dot: 4,
+            label: None,
+        }];
+
+        #[rustfmt::skip]
+        let expected = [
+            [false, false],
+            [false,  true],
+            [false, false],
+            [false, false],
+        ];
+
+        // The last line is past the end of the input
+        assert_eq!(
+            expected,
+            Foo::bar_reference_dots::<2, 4>(&references, 1, 5..8)
+        );
+        assert_eq!(
+            [[false; 2]; 4],
+            Foo::bar_reference_dots::<2, 4>(&references, 0, 5..8)
+        );
+    }
+
+    #[test]
+    fn bar_reference_header() {
+        struct Foo;
+        impl ReferenceLinable for Foo {}
+
+        let references = [
+            ScaledReference {
+                dot: 11,
+                label: Some("b"),
+            },
+            ScaledReference {
+                dot: 3,
+                label: Some("aaaaa"),
+            },
+            ScaledReference {
+                dot: 1,
+                label: None,
+            },
+        ];
+
+        assert_eq!(
+            Some(String::from(" aaaaa b")),
+            Foo::bar_reference_header::<2>(&references)
+        );
+        assert_eq!(None, Foo::bar_reference_header::<2>(&references[2..]));
+    }
+
     #[test]
     fn try_build_braille_row() {
         struct Foo;
@@ -3,3 +3,57 @@ mod columns;
 
 pub use bars::Bars;
 pub use columns::Columns;
+
+use crate::graph::STACK_SHADES;
+
+/// Fill the blocks between the running total at each value in a line and the one before it, with
+/// the shade of each series
+///
+/// Totals are scaled to eighths of a block, like the values of a bar, and rounded to the nearest
+/// block, since the shades can't be partially filled. The first series starts from the block at
+/// `zero`. Missing values don't contribute anything to the stack.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn stacked_blocks(
+    values: impl IntoIterator<Item = Option<f64>>,
+    scale: impl Fn(f64) -> f64,
+    zero: usize,
+) -> Vec<&'static str> {
+    let mut blocks = vec![];
+    let mut total = 0.;
+    let mut previous = zero;
+    for (series, value) in values.into_iter().enumerate() {
+        let Some(value) = value else {
+            continue;
+        };
+
+        total += value;
+        let end = (scale(total) / 8.).round() as usize;
+        let (start, stop) = (previous.min(end), previous.max(end));
+        if blocks.len() < stop {
+            blocks.resize(stop, " ");
+        }
+
+        blocks[start..stop].fill(STACK_SHADES[series % STACK_SHADES.len()]);
+        previous = end;
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stacked_blocks_with_shades() {
+        let scale = |value: f64| value * 8.;
+        assert_eq!(
+            vec!["█", "▒", "▒", "░"],
+            stacked_blocks([Some(1.), Some(2.), Some(1.)], scale, 0)
+        );
+        assert_eq!(
+            vec!["█", "░", "░"],
+            stacked_blocks([Some(3.), None, Some(-2.)], scale, 0)
+        );
+    }
+}
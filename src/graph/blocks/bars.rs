@@ -39,24 +39,59 @@ impl Graphable<Option<f64>> for Bars {
         let minimum = <Self as Graphable<Option<f64>>>::minimum(self);
         let maximum = <Self as Graphable<Option<f64>>>::maximum(self);
         let width = <Self as BarGraphable<Option<f64>>>::width(self);
+        let baseline = <Self as Graphable<Option<f64>>>::baseline(self);
 
-        let min = 1.; // reserve an empty line for null values
-        let max = f64::from(width * 8); // braille characters are 2 dots wide
-        let slope = (max - min) / (maximum - minimum);
-        let scale = |value: f64| {
-            assert!(
-                value >= minimum && value <= maximum,
-                "value out of bounds: {value} [{minimum}, {maximum}]"
-            );
-            min + slope * (value - minimum)
-        };
+        let scale = |value| Self::scale(value, minimum, maximum, width);
+
+        // Blocks can only be partially filled from the left, so start from the nearest full block
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let baseline = baseline
+            .map(|baseline| (scale(baseline.clamp(minimum, maximum)) / 8.).round() as usize);
+
+        for line in input_lines {
+            let value = line?.into_inner().map(scale);
+            let line = match baseline {
+                Some(baseline) => Self::print_line_from_baseline(value, baseline),
+                None => Self::print_line(value),
+            };
+
+            writeln!(writer, "{line}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl BarGraphable<Vec<Option<f64>>> for Bars {}
+impl Graphable<Vec<Option<f64>>> for Bars {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Stacked series, each filling whole blocks with its own shade
+    fn print_graph<W: Write>(
+        &self,
+        input_lines: ValueIter<Vec<Option<f64>>>,
+        mut writer: LineWriter<W>,
+    ) -> anyhow::Result<()> {
+        let minimum = <Self as Graphable<Vec<Option<f64>>>>::minimum(self);
+        let maximum = <Self as Graphable<Vec<Option<f64>>>>::maximum(self);
+        let stack = <Self as Graphable<Vec<Option<f64>>>>::stack(self);
+        let width = <Self as BarGraphable<Vec<Option<f64>>>>::width(self);
+
+        anyhow::ensure!(
+            stack,
+            "Multiple values per line not supported for this graph kind"
+        );
+
+        let scale = |value| Self::scale(value, minimum, maximum, width);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let zero = (scale(0_f64.clamp(minimum, maximum)) / 8.).round() as usize;
 
         for line in input_lines {
-            writeln!(
-                writer,
-                "{}",
-                Self::print_line(line?.into_inner().map(scale))
-            )?;
+            let blocks = super::stacked_blocks(line?, scale, zero);
+            writeln!(writer, "{}", blocks.concat().trim_end())?;
         }
 
         Ok(())
@@ -76,6 +111,16 @@ impl Bars {
         "\u{2588}", // █
     ];
 
+    /// Scale a value to the eighths of a block it fills, keeping the first for null values
+    fn scale(value: f64, minimum: f64, maximum: f64, width: u16) -> f64 {
+        assert!(
+            value >= minimum && value <= maximum,
+            "value out of bounds: {value} [{minimum}, {maximum}]"
+        );
+        let (min, max) = (1., f64::from(width * 8));
+        min + (max - min) / (maximum - minimum) * (value - minimum)
+    }
+
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn print_line(value: Option<f64>) -> String {
         if let Some(value) = value {
@@ -89,6 +134,31 @@ impl Bars {
             String::new()
         }
     }
+
+    /// Print a bar starting from the block at `baseline`, filling full blocks for values below it
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn print_line_from_baseline(value: Option<f64>, baseline: usize) -> String {
+        let Some(value) = value else {
+            return String::new();
+        };
+
+        let origin = baseline as f64 * 8.;
+        if value >= origin {
+            let mut line = " ".repeat(baseline);
+            line.push_str(&Self::print_line(Some(value - origin)));
+            line
+        } else {
+            let start = (value / 8.).round() as usize;
+            let full_block = Self::BLOCKS.last().unwrap();
+            let mut line = " ".repeat(start);
+            line.push_str(&full_block.repeat(baseline - start));
+            line
+        }
+    }
 }
 
 #[cfg(test)]
@@ -114,4 +184,24 @@ mod tests {
     t!(bars_print_line_6, "▊", Some(6.));
     t!(bars_print_line_7, "▉", Some(7.));
     t!(bars_print_line_8, "█", Some(8.));
+
+    macro_rules! tb {
+        ($name:ident, $expected:literal, $value:expr, $baseline:literal) => {
+            #[test]
+            fn $name() {
+                assert_eq!($expected, Bars::print_line_from_baseline($value, $baseline));
+            }
+        };
+    }
+
+    tb!(bars_print_line_from_baseline_none, "", None, 2);
+    tb!(bars_print_line_from_baseline_at, "  ", Some(16.), 2);
+    tb!(bars_print_line_from_baseline_above, "  █▍", Some(27.), 2);
+    tb!(bars_print_line_from_baseline_below, " █", Some(10.), 2);
+    tb!(
+        bars_print_line_from_baseline_below_rounded,
+        "  ",
+        Some(13.),
+        2
+    );
 }
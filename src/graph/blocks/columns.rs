@@ -23,40 +23,69 @@ impl Graphable<Option<f64>> for Columns {
     fn print_graph<W: Write>(
         &self,
         lines: ValueIter<Option<f64>>,
-        mut writer: LineWriter<W>,
+        writer: LineWriter<W>,
     ) -> anyhow::Result<()> {
         let lines: Vec<_> = lines.into_iter().collect();
         let minimum = <Self as Graphable<Option<f64>>>::minimum(self);
         let maximum = <Self as Graphable<Option<f64>>>::maximum(self);
         let height = <Self as ColumnGraphable<Option<f64>>>::height(self);
-
-        let min = 1.;
-        let max = f64::from(height * 8);
-        let slope = (max - min) / (maximum - minimum);
+        let baseline = <Self as Graphable<Option<f64>>>::baseline(self);
 
         let mut columns = Vec::with_capacity(lines.len());
 
-        let scale = |value: f64| {
-            assert!(
-                value >= minimum && value <= maximum,
-                "value out of bounds: {value} [{minimum}, {maximum}]"
-            );
-            min + slope * (value - minimum)
-        };
+        let scale = |value| Self::scale(value, minimum, maximum, height);
+
+        // Blocks can only be partially filled from the bottom, so start from the nearest full block
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let baseline = baseline
+            .map(|baseline| (scale(baseline.clamp(minimum, maximum)) / 8.).round() as usize);
 
         for line in lines {
-            let column = Self::calculate_column(line?.into_inner().map(scale));
+            let value = line?.into_inner().map(scale);
+            let column = match baseline {
+                Some(baseline) => Self::calculate_column_from_baseline(value, baseline),
+                None => Self::calculate_column(value),
+            };
             columns.push(column);
         }
 
-        for row in (0..usize::from(height)).rev() {
-            for column in &columns {
-                write!(writer, "{}", column.get(row).unwrap_or(&" "))?;
-            }
-            writeln!(writer)?;
-        }
+        Self::print_rows(writer, &columns, height)
+    }
+}
 
-        Ok(())
+impl ColumnGraphable<Vec<Option<f64>>> for Columns {}
+impl Graphable<Vec<Option<f64>>> for Columns {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Stacked series, each filling whole blocks with its own shade
+    fn print_graph<W: Write>(
+        &self,
+        lines: ValueIter<Vec<Option<f64>>>,
+        writer: LineWriter<W>,
+    ) -> anyhow::Result<()> {
+        let minimum = <Self as Graphable<Vec<Option<f64>>>>::minimum(self);
+        let maximum = <Self as Graphable<Vec<Option<f64>>>>::maximum(self);
+        let stack = <Self as Graphable<Vec<Option<f64>>>>::stack(self);
+        let height = <Self as ColumnGraphable<Vec<Option<f64>>>>::height(self);
+
+        anyhow::ensure!(
+            stack,
+            "Multiple values per line not supported for this graph kind"
+        );
+
+        let scale = |value| Self::scale(value, minimum, maximum, height);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let zero = (scale(0_f64.clamp(minimum, maximum)) / 8.).round() as usize;
+
+        let columns = lines
+            .into_iter()
+            .map(|line| Ok(super::stacked_blocks(line?, scale, zero)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        Self::print_rows(writer, &columns, height)
     }
 }
 
@@ -73,6 +102,32 @@ impl Columns {
         "\u{2588}", // █
     ];
 
+    /// Scale a value to the eighths of a block it fills, keeping the first for null values
+    fn scale(value: f64, minimum: f64, maximum: f64, height: u16) -> f64 {
+        assert!(
+            value >= minimum && value <= maximum,
+            "value out of bounds: {value} [{minimum}, {maximum}]"
+        );
+        let (min, max) = (1., f64::from(height * 8));
+        min + (max - min) / (maximum - minimum) * (value - minimum)
+    }
+
+    /// Print the columns from the top row down
+    fn print_rows<W: Write>(
+        mut writer: LineWriter<W>,
+        columns: &[Vec<&str>],
+        height: u16,
+    ) -> anyhow::Result<()> {
+        for row in (0..usize::from(height)).rev() {
+            for column in columns {
+                write!(writer, "{}", column.get(row).unwrap_or(&" "))?;
+            }
+            writeln!(writer)?;
+        }
+
+        Ok(())
+    }
+
     #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
     fn calculate_column(value: Option<f64>) -> Vec<&'static str> {
         if let Some(value) = value {
@@ -89,4 +144,50 @@ impl Columns {
             vec![" "]
         }
     }
+
+    /// Calculate a column starting from the block at `baseline`, filling full blocks for values
+    /// below it
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_precision_loss,
+        clippy::cast_sign_loss
+    )]
+    fn calculate_column_from_baseline(value: Option<f64>, baseline: usize) -> Vec<&'static str> {
+        let Some(value) = value else {
+            return vec![" "];
+        };
+
+        let origin = baseline as f64 * 8.;
+        let mut column = vec![" "; baseline];
+        if value >= origin {
+            column.extend(Self::calculate_column(Some(value - origin)));
+        } else {
+            let start = (value / 8.).round() as usize;
+            let full_block = *Self::BLOCKS.last().unwrap();
+            column[start..].fill(full_block);
+        }
+
+        column
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn column_from_baseline_above() {
+        assert_eq!(
+            vec![" ", " ", "█", "▃"],
+            Columns::calculate_column_from_baseline(Some(27.), 2)
+        );
+    }
+
+    #[test]
+    fn column_from_baseline_below() {
+        assert_eq!(
+            vec![" ", "█"],
+            Columns::calculate_column_from_baseline(Some(10.), 2)
+        );
+    }
 }
@@ -1,10 +1,18 @@
-mod columns;
-mod lines;
+use crate::GraphStyle;
+use crate::graph::glyphs::{self, GlyphSet};
 
-pub use columns::Columns;
-pub use lines::Lines;
+/// Braille patterns, 2 dots wide and 4 tall
+#[derive(Debug)]
+pub struct Braille;
 
-use crate::GraphStyle;
+impl GlyphSet<4> for Braille {
+    fn glyph(dots: [[bool; glyphs::WIDTH]; 4]) -> char {
+        Char::new(dots).as_char()
+    }
+}
+
+pub type Columns = glyphs::Columns<Braille, 4>;
+pub type Lines = glyphs::Lines<Braille, 4>;
 
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Char {
@@ -0,0 +1,42 @@
+//! Graphs drawn with any set of characters that show a block of dots.
+//!
+//! Each glyph set only maps the dots of one character to the character showing them, and the bar
+//! and column graphs here work the same way for every set.
+
+mod columns;
+mod lines;
+
+pub use columns::Columns;
+pub use lines::Lines;
+
+/// How many dots wide the characters of every glyph set are
+///
+/// The bar and column graphs draw two values side by side in each character, so every set is
+/// this wide, and only their height differs.
+pub const WIDTH: usize = 2;
+
+/// A set of characters that each show a block of dots, [`WIDTH`] wide and `ROWS` tall
+pub trait GlyphSet<const ROWS: usize> {
+    /// The character showing these dots, given from the top row down
+    fn glyph(dots: [[bool; WIDTH]; ROWS]) -> char;
+}
+
+/// How many dots wide and tall each character of a glyph set is
+#[must_use]
+pub const fn cell_size<G: GlyphSet<ROWS>, const ROWS: usize>() -> (usize, usize) {
+    (WIDTH, ROWS)
+}
+
+/// Set every dot that's set in either block of dots
+fn union<const ROWS: usize>(
+    mut dots: [[bool; WIDTH]; ROWS],
+    other: [[bool; WIDTH]; ROWS],
+) -> [[bool; WIDTH]; ROWS] {
+    for (row, other_row) in dots.iter_mut().zip(other) {
+        for (dot, other_dot) in row.iter_mut().zip(other_row) {
+            *dot |= other_dot;
+        }
+    }
+
+    dots
+}
@@ -1,27 +1,39 @@
 use std::io::{LineWriter, Write};
+use std::marker::PhantomData;
+use std::str::FromStr;
 
-use super::Brailleish;
-use super::Char as BrailleChar;
+use super::{GlyphSet, WIDTH};
 use crate::Config;
 use crate::GraphStyle;
 use crate::InputLine;
+use crate::graph::Stackable;
+use crate::graph::braille::Brailleish;
+use crate::graph::{ReferenceLinable, ScaledReference};
 use crate::opt::ValueIter;
 use crate::{ColumnGraphable, Graphable};
 
-pub struct Columns {
+/// A column graph drawn with the glyph set `G`, whose characters are `ROWS` dots tall
+#[derive(Debug)]
+pub struct Columns<G, const ROWS: usize> {
     config: Config,
+    glyphs: PhantomData<G>,
 }
 
-impl From<Config> for Columns {
+impl<G, const ROWS: usize> From<Config> for Columns<G, ROWS> {
     fn from(config: Config) -> Self {
-        Self { config }
+        Self {
+            config,
+            glyphs: PhantomData,
+        }
     }
 }
 
-impl Brailleish<4> for Columns {}
+impl<G, const ROWS: usize> Brailleish<ROWS> for Columns<G, ROWS> {}
+impl<G, const ROWS: usize> Stackable for Columns<G, ROWS> {}
+impl<G, const ROWS: usize> ReferenceLinable for Columns<G, ROWS> {}
 
-impl ColumnGraphable<Option<f64>> for Columns {}
-impl Graphable<Option<f64>> for Columns {
+impl<G: GlyphSet<ROWS>, const ROWS: usize> ColumnGraphable<Option<f64>> for Columns<G, ROWS> {}
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Graphable<Option<f64>> for Columns<G, ROWS> {
     fn config(&self) -> &Config {
         &self.config
     }
@@ -37,47 +49,38 @@ impl Graphable<Option<f64>> for Columns {
         let height = <Self as ColumnGraphable<Option<f64>>>::height(self);
 
         let min = 1;
-        let max = height * 4;
+        let max = height * Self::rows();
         let scale = |value| Self::scale(value, minimum, maximum, min, max);
-
-        // Clamp where 0 would fit to be inside the output range
-        let zero = if minimum > 0. {
+        let references = Self::scale_references(
+            <Self as Graphable<Option<f64>, Config>>::references(self),
+            scale,
+        );
+
+        // Clamp where the baseline would fit to be inside the output range
+        let baseline = <Self as Graphable<Option<f64>, Config>>::baseline(self).unwrap_or_default();
+        let zero = if minimum > baseline {
             min
-        } else if maximum < 0. {
+        } else if maximum < baseline {
             max
         } else {
-            scale(0.)
+            scale(baseline)
         };
 
-        let mut input_lines = lines.into_iter();
-
-        let mut column_quads = vec![];
-
-        loop {
-            let left = input_lines.next();
-            let right = input_lines.next();
-            if left.is_none() {
-                break;
-            }
-
-            let mut column = [vec![], vec![]];
-            for (i, side) in [left, right].into_iter().enumerate() {
-                if let Some(value) = side.transpose()?.and_then(InputLine::into_inner).map(scale) {
-                    column[i] = Self::into_dot_groups(value, zero, style);
-                }
-            }
-
-            column_quads.push(column);
-        }
-
-        Self::into_braille_rows(writer, &column_quads, usize::from(height))?;
-
-        Ok(())
+        Self::print_columns(lines, writer, height, &references, |line| {
+            line.into_inner()
+                .map(scale)
+                .map(|value| Self::into_dot_groups(value, zero, style))
+        })
     }
 }
 
-impl<const N: usize> ColumnGraphable<[Option<f64>; N]> for Columns {}
-impl<const N: usize> Graphable<[Option<f64>; N]> for Columns {
+impl<G: GlyphSet<ROWS>, const ROWS: usize, const N: usize> ColumnGraphable<[Option<f64>; N]>
+    for Columns<G, ROWS>
+{
+}
+impl<G: GlyphSet<ROWS>, const ROWS: usize, const N: usize> Graphable<[Option<f64>; N]>
+    for Columns<G, ROWS>
+{
     fn config(&self) -> &Config {
         &self.config
     }
@@ -93,45 +96,31 @@ impl<const N: usize> Graphable<[Option<f64>; N]> for Columns {
         let height = <Self as ColumnGraphable<[Option<f64>; N]>>::height(self);
 
         let min = 1;
-        let max = height * 4;
+        let max = height * Self::rows();
         let scale = |value: f64| Self::scale(value, minimum, maximum, min, max);
-
-        let mut input_lines = lines.into_iter();
-
-        let mut column_quads = vec![];
-
-        loop {
-            let left = input_lines.next();
-            let right = input_lines.next();
-            if left.is_none() {
-                break;
-            }
-
-            let mut column = [vec![], vec![]];
-            for (i, side) in [left, right].into_iter().enumerate() {
-                if let Some(value) = side.transpose()?.map(|input_line_value| {
-                    input_line_value
-                        .into_iter()
-                        .map(|x| x.map(scale).unwrap_or_default())
-                        .collect::<Vec<_>>()
-                        .try_into()
-                        .unwrap()
-                }) {
-                    column[i] = Self::into_dot_quads_from_array::<N>(value, style);
-                }
-            }
-
-            column_quads.push(column);
-        }
-
-        Self::into_braille_rows(writer, &column_quads, usize::from(height))?;
-
-        Ok(())
+        let references = Self::scale_references(
+            <Self as Graphable<[Option<f64>; N], Config>>::references(self),
+            scale,
+        );
+
+        // Lines missing one value only draw the other, and lines missing both are gaps
+        Self::print_columns(lines, writer, height, &references, |line| {
+            let values: Vec<_> = line.into_iter().flatten().collect();
+            let first = *values.first()?;
+            let last = *values.last()?;
+            let values: Vec<_> = (0..N)
+                .map(|index| scale(if index == 0 { first } else { last }))
+                .collect();
+            Some(Self::into_dot_groups_from_array::<N>(
+                values.try_into().unwrap(),
+                style,
+            ))
+        })
     }
 }
 
-impl ColumnGraphable<Vec<Option<f64>>> for Columns {}
-impl Graphable<Vec<Option<f64>>> for Columns {
+impl<G: GlyphSet<ROWS>, const ROWS: usize> ColumnGraphable<Vec<Option<f64>>> for Columns<G, ROWS> {}
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Graphable<Vec<Option<f64>>> for Columns<G, ROWS> {
     fn config(&self) -> &Config {
         &self.config
     }
@@ -144,27 +133,65 @@ impl Graphable<Vec<Option<f64>>> for Columns {
         let minimum = <Self as Graphable<Vec<Option<f64>>, Config>>::minimum(self);
         let maximum = <Self as Graphable<Vec<Option<f64>>, Config>>::maximum(self);
         let style = <Self as Graphable<Vec<Option<f64>>, Config>>::style(self);
+        let stack = <Self as Graphable<Vec<Option<f64>>, Config>>::stack(self);
         let height = <Self as ColumnGraphable<Vec<Option<f64>>>>::height(self);
-        // assert!(
-        //     matches!(style, GraphStyle::Line),
-        //     "Only line style is supported for graphs with more than 2 series"
-        // );
 
         let min = 1;
-        let max = height * 4;
+        let max = height * Self::rows();
         let scale = |value: f64| Self::scale(value, minimum, maximum, min, max);
-
-        let zero = if minimum > 0. {
+        let references = Self::scale_references(
+            <Self as Graphable<Vec<Option<f64>>, Config>>::references(self),
+            scale,
+        );
+
+        // Clamp where the baseline would fit to be inside the output range
+        let baseline =
+            <Self as Graphable<Vec<Option<f64>>, Config>>::baseline(self).unwrap_or_default();
+        let zero = if minimum > baseline {
             min
-        } else if maximum < 0. {
+        } else if maximum < baseline {
             max
         } else {
-            scale(0.)
+            scale(baseline)
         };
 
+        Self::print_columns(lines, writer, height, &references, |line| {
+            Some(if stack {
+                let totals = Self::scale_totals(line, scale);
+                Self::into_stacked_dot_groups(&totals, zero)
+            } else {
+                let value = line
+                    .into_iter()
+                    .filter_map(|x| x.map(scale))
+                    .collect::<Vec<_>>();
+                Self::into_dot_groups_from_array_multiple(&value, zero, style)
+            })
+        })
+    }
+}
+
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Columns<G, ROWS> {
+    /// How many dots tall each character is, to scale values to
+    fn rows() -> u16 {
+        u16::try_from(ROWS).expect("glyphs should be fewer than 65536 dots tall")
+    }
+
+    /// Print the graph, with `into_dots` turning each line of input into the dots up its
+    /// column, or nothing for a gap
+    fn print_columns<T: 'static, W: Write>(
+        lines: ValueIter<T>,
+        writer: LineWriter<W>,
+        height: u16,
+        references: &[ScaledReference],
+        mut into_dots: impl FnMut(InputLine<T>) -> Option<Vec<[bool; ROWS]>>,
+    ) -> anyhow::Result<()>
+    where
+        InputLine<T>: FromStr,
+        <InputLine<T> as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
         let mut input_lines = lines.into_iter();
 
-        let mut column_quads = vec![];
+        let mut column_groups = vec![];
 
         loop {
             let left = input_lines.next();
@@ -175,35 +202,29 @@ impl Graphable<Vec<Option<f64>>> for Columns {
 
             let mut column = [vec![], vec![]];
             for (i, side) in [left, right].into_iter().enumerate() {
-                if let Some(value) = side.transpose()?.map(|input_line_value| {
-                    input_line_value
-                        .into_iter()
-                        .filter_map(|x| x.map(scale))
-                        .collect::<Vec<_>>()
-                }) {
-                    column[i] = Self::into_dot_quads_from_array_multiple(&value, zero, style);
+                if let Some(dots) = side.transpose()?.and_then(&mut into_dots) {
+                    column[i] = dots;
                 }
             }
 
-            column_quads.push(column);
+            column_groups.push(column);
         }
 
-        Self::into_braille_rows(writer, &column_quads, usize::from(height))?;
+        Self::into_glyph_rows(writer, &column_groups, usize::from(height), references)?;
 
         Ok(())
     }
-}
 
-impl Columns {
-    fn into_braille_rows<W: Write>(
+    fn into_glyph_rows<W: Write>(
         mut line_writer: LineWriter<W>,
-        column_quads: &[[Vec<[bool; 4]>; 2]],
+        column_groups: &[[Vec<[bool; ROWS]>; WIDTH]],
         height: usize,
+        references: &[ScaledReference],
     ) -> std::io::Result<()> {
         for row_index in (0..height).rev() {
-            for col in column_quads {
-                let mut raw_braille_char = [[false; 2]; 4];
-                for (character_row, pair) in raw_braille_char.iter_mut().rev().enumerate() {
+            for col in column_groups {
+                let mut dots = [[false; WIDTH]; ROWS];
+                for (character_row, pair) in dots.iter_mut().rev().enumerate() {
                     let left = col
                         .first()
                         .and_then(|c| c.get(row_index))
@@ -219,23 +240,25 @@ impl Columns {
                     *pair = [left, right];
                 }
 
-                write!(
-                    line_writer,
-                    "{}",
-                    BrailleChar::new(raw_braille_char).as_char()
-                )?;
+                let reference = Self::column_reference_dots(references, row_index);
+                write!(line_writer, "{}", G::glyph(super::union(dots, reference)))?;
             }
 
+            write!(
+                line_writer,
+                "{}",
+                Self::column_reference_labels::<ROWS>(references, row_index)
+            )?;
             writeln!(line_writer)?;
         }
 
         Ok(())
     }
 
-    fn into_dot_quads_from_array<const N: usize>(
+    fn into_dot_groups_from_array<const N: usize>(
         line_set: [u16; N],
         style: GraphStyle,
-    ) -> Vec<[bool; 4]> {
+    ) -> Vec<[bool; ROWS]> {
         assert_eq!(2, line_set.len(), "Not yet supported");
         let start = line_set[0];
         let end = line_set[1];
@@ -263,25 +286,25 @@ impl Columns {
             }
         }
 
-        let chunks = iter.chunks_exact(4);
+        let chunks = iter.chunks_exact(ROWS);
         let mut tip = chunks.remainder().to_vec();
-        let mut column: Vec<[bool; 4]> = chunks
+        let mut column: Vec<[bool; ROWS]> = chunks
             .into_iter()
             .map(|chunk| chunk.try_into().unwrap())
             .collect();
         if !tip.is_empty() {
-            tip.resize(4, false);
+            tip.resize(ROWS, false);
             column.push(tip.try_into().unwrap());
         }
 
         column
     }
 
-    fn into_dot_quads_from_array_multiple(
+    fn into_dot_groups_from_array_multiple(
         line_set: &[u16],
         zero: u16,
         style: GraphStyle,
-    ) -> Vec<[bool; 4]> {
+    ) -> Vec<[bool; ROWS]> {
         if line_set.is_empty() {
             return vec![];
         }
@@ -328,14 +351,14 @@ impl Columns {
             }
         }
 
-        let chunks = iter.chunks_exact(4);
+        let chunks = iter.chunks_exact(ROWS);
         let mut tip = chunks.remainder().to_vec();
-        let mut column: Vec<[bool; 4]> = chunks
+        let mut column: Vec<[bool; ROWS]> = chunks
             .into_iter()
             .map(|chunk| chunk.try_into().unwrap())
             .collect();
         if !tip.is_empty() {
-            tip.resize(4, false);
+            tip.resize(ROWS, false);
             column.push(tip.try_into().unwrap());
         }
 
@@ -346,6 +369,7 @@ impl Columns {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::BrailleColumns as Columns;
 
     #[cfg(test)]
     fn as_dot_pairs(raw: char) -> [[bool; 2]; 4] {
@@ -447,7 +471,7 @@ mod tests {
         let mut buffer = vec![];
         {
             let line_writer = LineWriter::new(&mut buffer);
-            Columns::into_braille_rows(line_writer, &input, 2).unwrap();
+            Columns::into_glyph_rows(line_writer, &input, 2, &[]).unwrap();
         }
         let dot_pairs = String::from_utf8(buffer)
             .unwrap()
@@ -548,7 +572,7 @@ mod tests {
         let mut buffer = vec![];
         {
             let line_writer = LineWriter::new(&mut buffer);
-            Columns::into_braille_rows(line_writer, &input, 2).unwrap();
+            Columns::into_glyph_rows(line_writer, &input, 2, &[]).unwrap();
         }
         let dot_pairs = String::from_utf8(buffer)
             .unwrap()
@@ -0,0 +1,350 @@
+//! Turn a series of numbers into a bar graph, in any set of glyphs.
+//!
+//! # Example
+//!
+//! ## Sine graph
+//!
+//! ```console
+//! $ awk 'BEGIN { for (i = 0; i < 20; i++) { print sin(i / 3); } }' | braille 4
+//! ⠀⠀⠀⠀⠀⣷⣶⣤⣀
+//! ⠀⠀⠀⠀⠀⣿⣿⣿⡿⠟
+//! ⠀⠀⢀⣀⣤⡟⠉⠁
+//! ⣴⣿⣿⣿⣿⡇
+//! ⠀⠉⠛⠻⠿⡇
+//! ```
+//!
+//! ## Simple number sequence
+//!
+//! ```console
+//! $ seq -4 3 | braille 7
+//! ⠉⠛⠿⣿
+//! ⠀⠀⠀⢸⣶⣤⣀
+//! ```
+
+use std::io::{LineWriter, Write};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use super::{GlyphSet, WIDTH};
+use crate::graph::DotArrayable;
+use crate::graph::RowBuildable;
+use crate::graph::Stackable;
+use crate::graph::braille::Brailleish;
+use crate::graph::{BarGraphable, Graphable};
+use crate::graph::{ReferenceLinable, ScaledReference};
+use crate::opt::{Config, ValueIter};
+use crate::{InputLine, InputLineSinglable};
+
+/// A bar graph drawn with the glyph set `G`, whose characters are `ROWS` dots tall
+#[derive(Debug)]
+pub struct Lines<G, const ROWS: usize> {
+    config: Config,
+    glyphs: PhantomData<G>,
+}
+
+impl<G, const ROWS: usize> From<Config> for Lines<G, ROWS> {
+    fn from(config: Config) -> Self {
+        Self {
+            config,
+            glyphs: PhantomData,
+        }
+    }
+}
+
+impl<G, const ROWS: usize> RowBuildable for Lines<G, ROWS> {}
+impl<G, const ROWS: usize> DotArrayable for Lines<G, ROWS> {}
+impl<G, const ROWS: usize> Brailleish<2> for Lines<G, ROWS> {}
+impl<G, const ROWS: usize> Stackable for Lines<G, ROWS> {}
+impl<G, const ROWS: usize> ReferenceLinable for Lines<G, ROWS> {}
+
+impl<G: GlyphSet<ROWS>, const ROWS: usize> BarGraphable<Option<f64>> for Lines<G, ROWS> {}
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Graphable<Option<f64>> for Lines<G, ROWS> {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn print_graph<W: Write>(
+        &self,
+        input_lines: ValueIter<Option<f64>>,
+        writer: LineWriter<W>,
+    ) -> anyhow::Result<()> {
+        let minimum = <Self as Graphable<Option<f64>, Config>>::minimum(self);
+        let maximum = <Self as Graphable<Option<f64>, Config>>::maximum(self);
+        let width = <Self as BarGraphable<Option<f64>>>::width(self);
+
+        let min = 1; // reserve an empty line for null values
+        let max = width * 2; // every glyph is 2 dots wide
+        let scale = |value| Self::scale(value, minimum, maximum, min, max);
+        let references = Self::scale_references(
+            <Self as Graphable<Option<f64>, Config>>::references(self),
+            scale,
+        );
+
+        // Clamp where the baseline would fit to be inside the output range
+        let baseline = <Self as Graphable<Option<f64>, Config>>::baseline(self).unwrap_or_default();
+        let zero = if minimum > baseline {
+            min
+        } else if maximum < baseline {
+            max
+        } else {
+            scale(baseline)
+        };
+
+        let style = <Self as Graphable<Option<f64>, Config>>::style(self);
+
+        Self::print_rows(input_lines, writer, &references, |line| {
+            line.into_inner()
+                .map(scale)
+                .map(|value| Self::into_dot_groups(value, zero, style))
+        })
+    }
+}
+
+impl<G: GlyphSet<ROWS>, const ROWS: usize, const N: usize> BarGraphable<[Option<f64>; N]>
+    for Lines<G, ROWS>
+{
+}
+impl<G: GlyphSet<ROWS>, const ROWS: usize, const N: usize> Graphable<[Option<f64>; N]>
+    for Lines<G, ROWS>
+{
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn print_graph<W: Write>(
+        &self,
+        input_lines: ValueIter<[Option<f64>; N]>,
+        writer: LineWriter<W>,
+    ) -> anyhow::Result<()> {
+        let minimum = <Self as Graphable<Option<f64>, _>>::minimum(self);
+        let maximum = <Self as Graphable<Option<f64>, _>>::maximum(self);
+        let style = <Self as Graphable<Option<f64>, _>>::style(self);
+        let width = <Self as BarGraphable<[Option<f64>; N]>>::width(self);
+
+        let min = 1; // reserve an empty line for null values
+        let max = width * 2; // every glyph is 2 dots wide
+        let scale = |value| Self::scale(value, minimum, maximum, min, max);
+        let references =
+            Self::scale_references(<Self as Graphable<Option<f64>, _>>::references(self), scale);
+
+        Self::print_rows(input_lines, writer, &references, |line| {
+            if line.as_single_iter().all(Option::is_none) {
+                None
+            } else {
+                let line = line
+                    .into_iter()
+                    .map(|x| scale(x.unwrap()))
+                    .collect::<Vec<_>>();
+                let line = <[_; N]>::try_from(line).unwrap();
+                Some(Self::into_dot_array_groups(line, style))
+            }
+        })
+    }
+}
+
+impl<G: GlyphSet<ROWS>, const ROWS: usize> BarGraphable<Vec<Option<f64>>> for Lines<G, ROWS> {}
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Graphable<Vec<Option<f64>>> for Lines<G, ROWS> {
+    fn config(&self) -> &Config {
+        &self.config
+    }
+
+    fn print_graph<W: Write>(
+        &self,
+        input_lines: ValueIter<Vec<Option<f64>>>,
+        writer: LineWriter<W>,
+    ) -> anyhow::Result<()> {
+        let minimum = <Self as Graphable<Vec<Option<f64>>, Config>>::minimum(self);
+        let maximum = <Self as Graphable<Vec<Option<f64>>, Config>>::maximum(self);
+        let stack = <Self as Graphable<Vec<Option<f64>>, Config>>::stack(self);
+        let width = <Self as BarGraphable<Vec<Option<f64>>>>::width(self);
+
+        anyhow::ensure!(
+            stack,
+            "Multiple values per line not supported for this graph kind"
+        );
+
+        let min = 1; // reserve an empty line for null values
+        let max = width * 2; // every glyph is 2 dots wide
+        let scale = |value| Self::scale(value, minimum, maximum, min, max);
+        let references = Self::scale_references(
+            <Self as Graphable<Vec<Option<f64>>, Config>>::references(self),
+            scale,
+        );
+
+        // Clamp where the baseline would fit to be inside the output range
+        let baseline =
+            <Self as Graphable<Vec<Option<f64>>, Config>>::baseline(self).unwrap_or_default();
+        let zero = if minimum > baseline {
+            min
+        } else if maximum < baseline {
+            max
+        } else {
+            scale(baseline)
+        };
+
+        Self::print_rows(input_lines, writer, &references, |line| {
+            let totals = Self::scale_totals(line, scale);
+            Some(Self::into_stacked_dot_groups(&totals, zero))
+        })
+    }
+}
+
+impl<G: GlyphSet<ROWS>, const ROWS: usize> Lines<G, ROWS> {
+    /// Print the graph a row of glyphs at a time, with `into_dots` turning each line of input
+    /// into the dots along it, or nothing for a gap
+    fn print_rows<T: 'static, W: Write>(
+        input_lines: ValueIter<T>,
+        mut writer: LineWriter<W>,
+        references: &[ScaledReference],
+        mut into_dots: impl FnMut(InputLine<T>) -> Option<Vec<[bool; WIDTH]>>,
+    ) -> anyhow::Result<()>
+    where
+        InputLine<T>: FromStr,
+        <InputLine<T> as FromStr>::Err: std::error::Error + Send + Sync + 'static,
+    {
+        if let Some(header) = Self::bar_reference_header::<2>(references) {
+            writeln!(writer, "{header}")?;
+        }
+
+        let reference_width = Self::bar_reference_width::<2>(references);
+        let mut input_lines = input_lines.into_iter();
+        let mut line = 0;
+
+        // Each line of input is one row of dots
+        let mut buffer: [Vec<[bool; WIDTH]>; ROWS] = std::array::from_fn(|_| vec![]);
+        let mut has_more_lines = true;
+        while has_more_lines {
+            let first_line = line;
+            for buffer_line in &mut buffer {
+                let input_line = input_lines.next();
+                if input_line.is_none() {
+                    has_more_lines = false;
+                } else {
+                    line += 1;
+                }
+
+                if let Some(new_line) = input_line.transpose()?.and_then(&mut into_dots) {
+                    *buffer_line = new_line;
+                }
+            }
+
+            if has_more_lines || buffer.iter().any(|x| !x.is_empty()) {
+                let mut transposed = Self::assemble_row(&buffer);
+                transposed.resize(
+                    transposed.len().max(reference_width),
+                    [[false; WIDTH]; ROWS],
+                );
+                let glyph_line = transposed
+                    .into_iter()
+                    .enumerate()
+                    .map(|(column, x)| {
+                        let reference =
+                            Self::bar_reference_dots(references, column, first_line..line);
+                        G::glyph(super::union(x, reference))
+                    })
+                    .collect::<String>();
+                writeln!(writer, "{glyph_line}")?;
+            }
+
+            buffer.fill(vec![]);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::BrailleLines as Lines;
+    use crate::opt::GraphStyle;
+
+    #[test]
+    fn test_into_dot_pairs() {
+        assert_eq!(
+            vec![[false, false], [false, false], [true, false]],
+            Lines::into_dot_groups(5, 5, GraphStyle::default())
+        );
+    }
+
+    #[test]
+    fn test_transpose_row_line_1() {
+        #[rustfmt::skip]
+        let input = [
+            vec![[ true,  true], [ true, true], [true, false]],
+            vec![[false,  true], [ true, true], [true, false]],
+            vec![[false, false], [ true, true], [true, false]],
+            vec![[false, false], [false, true], [true, false]],
+        ];
+
+        #[rustfmt::skip]
+        let expected = vec![
+            [
+                [ true,  true],
+                [false,  true],
+                [false, false],
+                [false, false],
+            ],
+            [
+                [ true,  true],
+                [ true,  true],
+                [ true,  true],
+                [false,  true],
+            ],
+            [
+                [ true, false],
+                [ true, false],
+                [ true, false],
+                [ true, false],
+            ],
+        ];
+        let actual = Lines::assemble_row::<2, 4>(&input);
+        for (ex, act) in expected.iter().zip(actual.iter()) {
+            eprintln!("{ex:?}");
+            eprintln!("{act:?}");
+            eprintln!();
+        }
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_transpose_row_line_2() {
+        #[rustfmt::skip]
+        let input = [
+            vec![[false, false], [false, false], [true, false]               ],
+            vec![[false, false], [false, false], [true,  true]               ],
+            vec![[false, false], [false, false], [true,  true], [true, false]],
+            vec![[false, false], [false, false], [true,  true], [true,  true]],
+        ];
+
+        #[rustfmt::skip]
+        let expected = vec![
+            [
+                [false, false],
+                [false, false],
+                [false, false],
+                [false, false],
+            ],
+            [
+                [false, false],
+                [false, false],
+                [false, false],
+                [false, false],
+            ],
+            [
+                [ true, false],
+                [ true,  true],
+                [ true,  true],
+                [ true,  true]
+            ],
+            [
+                [false, false],
+                [false, false],
+                [ true, false],
+                [ true,  true],
+            ],
+        ];
+
+        assert_eq!(expected, Lines::assemble_row::<2, 4>(&input));
+    }
+}
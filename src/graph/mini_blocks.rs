@@ -1,45 +1,31 @@
-mod columns;
-mod lines;
+use crate::graph::glyphs::{self, GlyphSet};
 
-pub use columns::Columns;
-pub use lines::Lines;
-use std::fmt;
+/// Quadrants, 2 dots wide and 2 tall
+#[derive(Debug)]
+pub struct Quadrants;
 
-struct Char {
-    inner: &'static str,
-}
-
-impl Char {
-    pub fn new(dots: [[bool; 2]; 2]) -> Self {
-        Self {
-            inner: match dots {
-                [[false, false], [false, false]] => " ",
-                [[false, false], [false, true]] => "▗",
-                [[false, false], [true, false]] => "▖",
-                [[false, false], [true, true]] => "▄",
-                [[false, true], [false, false]] => "▝",
-                [[false, true], [false, true]] => "▐",
-                [[false, true], [true, false]] => "▞",
-                [[false, true], [true, true]] => "▟",
-                [[true, false], [false, false]] => "▘",
-                [[true, false], [false, true]] => "▚",
-                [[true, false], [true, false]] => "▌",
-                [[true, false], [true, true]] => "▙",
-                [[true, true], [false, false]] => "▀",
-                [[true, true], [false, true]] => "▜",
-                [[true, true], [true, false]] => "▛",
-                [[true, true], [true, true]] => "█",
-            },
+impl GlyphSet<2> for Quadrants {
+    fn glyph(dots: [[bool; glyphs::WIDTH]; 2]) -> char {
+        match dots {
+            [[false, false], [false, false]] => ' ',
+            [[false, false], [false, true]] => '▗',
+            [[false, false], [true, false]] => '▖',
+            [[false, false], [true, true]] => '▄',
+            [[false, true], [false, false]] => '▝',
+            [[false, true], [false, true]] => '▐',
+            [[false, true], [true, false]] => '▞',
+            [[false, true], [true, true]] => '▟',
+            [[true, false], [false, false]] => '▘',
+            [[true, false], [false, true]] => '▚',
+            [[true, false], [true, false]] => '▌',
+            [[true, false], [true, true]] => '▙',
+            [[true, true], [false, false]] => '▀',
+            [[true, true], [false, true]] => '▜',
+            [[true, true], [true, false]] => '▛',
+            [[true, true], [true, true]] => '█',
         }
     }
-
-    pub fn as_str(&self) -> &'static str {
-        self.inner
-    }
 }
 
-impl fmt::Display for Char {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
-    }
-}
+pub type Columns = glyphs::Columns<Quadrants, 2>;
+pub type Lines = glyphs::Lines<Quadrants, 2>;
@@ -1,286 +1,272 @@
-mod columns;
-mod lines;
+use crate::graph::glyphs::{self, GlyphSet};
 
-pub use columns::Columns;
-pub use lines::Lines;
-use std::fmt;
+/// Octants, 2 dots wide and 4 tall
+#[derive(Debug)]
+pub struct Octants;
 
-struct Char {
-    inner: &'static str,
-}
-
-impl Char {
-    pub fn new(dots: [[bool; 2]; 4]) -> Self {
-        Self {
-            #[rustfmt::skip]
-            inner: match dots {
-                [[false, false], [false, false], [false, false], [false, false]] => " ",
-                [[true, false], [false, false], [false, false], [false, false]] => "𜺨",
-                [[false, true], [false, false], [false, false], [false, false]] => "𜺫",
-                [[true, true], [false, false], [false, false], [false, false]] => "🮂",
-                [[false, false], [true, false], [false, false], [false, false]] => "𜴀",
-                [[true, false], [true, false], [false, false], [false, false]] => "▘",
-                [[false, true], [true, false], [false, false], [false, false]] => "𜴁",
-                [[true, true], [true, false], [false, false], [false, false]] => "𜴂",
-                [[false, false], [false, true], [false, false], [false, false]] => "𜴃",
-                [[true, false], [false, true], [false, false], [false, false]] => "𜴄",
-                [[false, true], [false, true], [false, false], [false, false]] => "▝",
-                [[true, true], [false, true], [false, false], [false, false]] => "𜴅",
-                [[false, false], [true, true], [false, false], [false, false]] => "𜴆",
-                [[true, false], [true, true], [false, false], [false, false]] => "𜴇",
-                [[false, true], [true, true], [false, false], [false, false]] => "𜴈",
-                [[true, true], [true, true], [false, false], [false, false]] => "▀",
-                [[false, false], [false, false], [true, false], [false, false]] => "𜴉",
-                [[true, false], [false, false], [true, false], [false, false]] => "𜴊",
-                [[false, true], [false, false], [true, false], [false, false]] => "𜴋",
-                [[true, true], [false, false], [true, false], [false, false]] => "𜴌",
-                [[false, false], [true, false], [true, false], [false, false]] => "🯦",
-                [[true, false], [true, false], [true, false], [false, false]] => "𜴍",
-                [[false, true], [true, false], [true, false], [false, false]] => "𜴎",
-                [[true, true], [true, false], [true, false], [false, false]] => "𜴏",
-                [[false, false], [false, true], [true, false], [false, false]] => "𜴐",
-                [[true, false], [false, true], [true, false], [false, false]] => "𜴑",
-                [[false, true], [false, true], [true, false], [false, false]] => "𜴒",
-                [[true, true], [false, true], [true, false], [false, false]] => "𜴓",
-                [[false, false], [true, true], [true, false], [false, false]] => "𜴔",
-                [[true, false], [true, true], [true, false], [false, false]] => "𜴕",
-                [[false, true], [true, true], [true, false], [false, false]] => "𜴖",
-                [[true, true], [true, true], [true, false], [false, false]] => "𜴗",
-                [[false, false], [false, false], [false, true], [false, false]] => "𜴘",
-                [[true, false], [false, false], [false, true], [false, false]] => "𜴙",
-                [[false, true], [false, false], [false, true], [false, false]] => "𜴚",
-                [[true, true], [false, false], [false, true], [false, false]] => "𜴛",
-                [[false, false], [true, false], [false, true], [false, false]] => "𜴜",
-                [[true, false], [true, false], [false, true], [false, false]] => "𜴝",
-                [[false, true], [true, false], [false, true], [false, false]] => "𜴞",
-                [[true, true], [true, false], [false, true], [false, false]] => "𜴟",
-                [[false, false], [false, true], [false, true], [false, false]] => "🯧",
-                [[true, false], [false, true], [false, true], [false, false]] => "𜴠",
-                [[false, true], [false, true], [false, true], [false, false]] => "𜴡",
-                [[true, true], [false, true], [false, true], [false, false]] => "𜴢",
-                [[false, false], [true, true], [false, true], [false, false]] => "𜴣",
-                [[true, false], [true, true], [false, true], [false, false]] => "𜴤",
-                [[false, true], [true, true], [false, true], [false, false]] => "𜴥",
-                [[true, true], [true, true], [false, true], [false, false]] => "𜴦",
-                [[false, false], [false, false], [true, true], [false, false]] => "𜴧",
-                [[true, false], [false, false], [true, true], [false, false]] => "𜴨",
-                [[false, true], [false, false], [true, true], [false, false]] => "𜴩",
-                [[true, true], [false, false], [true, true], [false, false]] => "𜴪",
-                [[false, false], [true, false], [true, true], [false, false]] => "𜴫",
-                [[true, false], [true, false], [true, true], [false, false]] => "𜴬",
-                [[false, true], [true, false], [true, true], [false, false]] => "𜴭",
-                [[true, true], [true, false], [true, true], [false, false]] => "𜴮",
-                [[false, false], [false, true], [true, true], [false, false]] => "𜴯",
-                [[true, false], [false, true], [true, true], [false, false]] => "𜴰",
-                [[false, true], [false, true], [true, true], [false, false]] => "𜴱",
-                [[true, true], [false, true], [true, true], [false, false]] => "𜴲",
-                [[false, false], [true, true], [true, true], [false, false]] => "𜴳",
-                [[true, false], [true, true], [true, true], [false, false]] => "𜴴",
-                [[false, true], [true, true], [true, true], [false, false]] => "𜴵",
-                [[true, true], [true, true], [true, true], [false, false]] => "🮅",
-                [[false, false], [false, false], [false, false], [true, false]] => "𜺣",
-                [[true, false], [false, false], [false, false], [true, false]] => "𜴶",
-                [[false, true], [false, false], [false, false], [true, false]] => "𜴷",
-                [[true, true], [false, false], [false, false], [true, false]] => "𜴸",
-                [[false, false], [true, false], [false, false], [true, false]] => "𜴹",
-                [[true, false], [true, false], [false, false], [true, false]] => "𜴺",
-                [[false, true], [true, false], [false, false], [true, false]] => "𜴻",
-                [[true, true], [true, false], [false, false], [true, false]] => "𜴼",
-                [[false, false], [false, true], [false, false], [true, false]] => "𜴽",
-                [[true, false], [false, true], [false, false], [true, false]] => "𜴾",
-                [[false, true], [false, true], [false, false], [true, false]] => "𜴿",
-                [[true, true], [false, true], [false, false], [true, false]] => "𜵀",
-                [[false, false], [true, true], [false, false], [true, false]] => "𜵁",
-                [[true, false], [true, true], [false, false], [true, false]] => "𜵂",
-                [[false, true], [true, true], [false, false], [true, false]] => "𜵃",
-                [[true, true], [true, true], [false, false], [true, false]] => "𜵄",
-                [[false, false], [false, false], [true, false], [true, false]] => "▖",
-                [[true, false], [false, false], [true, false], [true, false]] => "𜵅",
-                [[false, true], [false, false], [true, false], [true, false]] => "𜵆",
-                [[true, true], [false, false], [true, false], [true, false]] => "𜵇",
-                [[false, false], [true, false], [true, false], [true, false]] => "𜵈",
-                [[true, false], [true, false], [true, false], [true, false]] => "▌",
-                [[false, true], [true, false], [true, false], [true, false]] => "𜵉",
-                [[true, true], [true, false], [true, false], [true, false]] => "𜵊",
-                [[false, false], [false, true], [true, false], [true, false]] => "𜵋",
-                [[true, false], [false, true], [true, false], [true, false]] => "𜵌",
-                [[false, true], [false, true], [true, false], [true, false]] => "▞",
-                [[true, true], [false, true], [true, false], [true, false]] => "𜵍",
-                [[false, false], [true, true], [true, false], [true, false]] => "𜵎",
-                [[true, false], [true, true], [true, false], [true, false]] => "𜵏",
-                [[false, true], [true, true], [true, false], [true, false]] => "𜵐",
-                [[true, true], [true, true], [true, false], [true, false]] => "▛",
-                [[false, false], [false, false], [false, true], [true, false]] => "𜵑",
-                [[true, false], [false, false], [false, true], [true, false]] => "𜵒",
-                [[false, true], [false, false], [false, true], [true, false]] => "𜵓",
-                [[true, true], [false, false], [false, true], [true, false]] => "𜵔",
-                [[false, false], [true, false], [false, true], [true, false]] => "𜵕",
-                [[true, false], [true, false], [false, true], [true, false]] => "𜵖",
-                [[false, true], [true, false], [false, true], [true, false]] => "𜵗",
-                [[true, true], [true, false], [false, true], [true, false]] => "𜵘",
-                [[false, false], [false, true], [false, true], [true, false]] => "𜵙",
-                [[true, false], [false, true], [false, true], [true, false]] => "𜵚",
-                [[false, true], [false, true], [false, true], [true, false]] => "𜵛",
-                [[true, true], [false, true], [false, true], [true, false]] => "𜵜",
-                [[false, false], [true, true], [false, true], [true, false]] => "𜵝",
-                [[true, false], [true, true], [false, true], [true, false]] => "𜵞",
-                [[false, true], [true, true], [false, true], [true, false]] => "𜵟",
-                [[true, true], [true, true], [false, true], [true, false]] => "𜵠",
-                [[false, false], [false, false], [true, true], [true, false]] => "𜵡",
-                [[true, false], [false, false], [true, true], [true, false]] => "𜵢",
-                [[false, true], [false, false], [true, true], [true, false]] => "𜵣",
-                [[true, true], [false, false], [true, true], [true, false]] => "𜵤",
-                [[false, false], [true, false], [true, true], [true, false]] => "𜵥",
-                [[true, false], [true, false], [true, true], [true, false]] => "𜵦",
-                [[false, true], [true, false], [true, true], [true, false]] => "𜵧",
-                [[true, true], [true, false], [true, true], [true, false]] => "𜵨",
-                [[false, false], [false, true], [true, true], [true, false]] => "𜵩",
-                [[true, false], [false, true], [true, true], [true, false]] => "𜵪",
-                [[false, true], [false, true], [true, true], [true, false]] => "𜵫",
-                [[true, true], [false, true], [true, true], [true, false]] => "𜵬",
-                [[false, false], [true, true], [true, true], [true, false]] => "𜵭",
-                [[true, false], [true, true], [true, true], [true, false]] => "𜵮",
-                [[false, true], [true, true], [true, true], [true, false]] => "𜵯",
-                [[true, true], [true, true], [true, true], [true, false]] => "𜵰",
-                [[false, false], [false, false], [false, false], [false, true]] => "𜺠",
-                [[true, false], [false, false], [false, false], [false, true]] => "𜵱",
-                [[false, true], [false, false], [false, false], [false, true]] => "𜵲",
-                [[true, true], [false, false], [false, false], [false, true]] => "𜵳",
-                [[false, false], [true, false], [false, false], [false, true]] => "𜵴",
-                [[true, false], [true, false], [false, false], [false, true]] => "𜵵",
-                [[false, true], [true, false], [false, false], [false, true]] => "𜵶",
-                [[true, true], [true, false], [false, false], [false, true]] => "𜵷",
-                [[false, false], [false, true], [false, false], [false, true]] => "𜵸",
-                [[true, false], [false, true], [false, false], [false, true]] => "𜵹",
-                [[false, true], [false, true], [false, false], [false, true]] => "𜵺",
-                [[true, true], [false, true], [false, false], [false, true]] => "𜵻",
-                [[false, false], [true, true], [false, false], [false, true]] => "𜵼",
-                [[true, false], [true, true], [false, false], [false, true]] => "𜵽",
-                [[false, true], [true, true], [false, false], [false, true]] => "𜵾",
-                [[true, true], [true, true], [false, false], [false, true]] => "𜵿",
-                [[false, false], [false, false], [true, false], [false, true]] => "𜶀",
-                [[true, false], [false, false], [true, false], [false, true]] => "𜶁",
-                [[false, true], [false, false], [true, false], [false, true]] => "𜶂",
-                [[true, true], [false, false], [true, false], [false, true]] => "𜶃",
-                [[false, false], [true, false], [true, false], [false, true]] => "𜶄",
-                [[true, false], [true, false], [true, false], [false, true]] => "𜶅",
-                [[false, true], [true, false], [true, false], [false, true]] => "𜶆",
-                [[true, true], [true, false], [true, false], [false, true]] => "𜶇",
-                [[false, false], [false, true], [true, false], [false, true]] => "𜶈",
-                [[true, false], [false, true], [true, false], [false, true]] => "𜶉",
-                [[false, true], [false, true], [true, false], [false, true]] => "𜶊",
-                [[true, true], [false, true], [true, false], [false, true]] => "𜶋",
-                [[false, false], [true, true], [true, false], [false, true]] => "𜶌",
-                [[true, false], [true, true], [true, false], [false, true]] => "𜶍",
-                [[false, true], [true, true], [true, false], [false, true]] => "𜶎",
-                [[true, true], [true, true], [true, false], [false, true]] => "𜶏",
-                [[false, false], [false, false], [false, true], [false, true]] => "▗",
-                [[true, false], [false, false], [false, true], [false, true]] => "𜶐",
-                [[false, true], [false, false], [false, true], [false, true]] => "𜶑",
-                [[true, true], [false, false], [false, true], [false, true]] => "𜶒",
-                [[false, false], [true, false], [false, true], [false, true]] => "𜶓",
-                [[true, false], [true, false], [false, true], [false, true]] => "▚",
-                [[false, true], [true, false], [false, true], [false, true]] => "𜶔",
-                [[true, true], [true, false], [false, true], [false, true]] => "𜶕",
-                [[false, false], [false, true], [false, true], [false, true]] => "𜶖",
-                [[true, false], [false, true], [false, true], [false, true]] => "𜶗",
-                [[false, true], [false, true], [false, true], [false, true]] => "▐",
-                [[true, true], [false, true], [false, true], [false, true]] => "𜶘",
-                [[false, false], [true, true], [false, true], [false, true]] => "𜶙",
-                [[true, false], [true, true], [false, true], [false, true]] => "𜶚",
-                [[false, true], [true, true], [false, true], [false, true]] => "𜶛",
-                [[true, true], [true, true], [false, true], [false, true]] => "▜",
-                [[false, false], [false, false], [true, true], [false, true]] => "𜶜",
-                [[true, false], [false, false], [true, true], [false, true]] => "𜶝",
-                [[false, true], [false, false], [true, true], [false, true]] => "𜶞",
-                [[true, true], [false, false], [true, true], [false, true]] => "𜶟",
-                [[false, false], [true, false], [true, true], [false, true]] => "𜶠",
-                [[true, false], [true, false], [true, true], [false, true]] => "𜶡",
-                [[false, true], [true, false], [true, true], [false, true]] => "𜶢",
-                [[true, true], [true, false], [true, true], [false, true]] => "𜶣",
-                [[false, false], [false, true], [true, true], [false, true]] => "𜶤",
-                [[true, false], [false, true], [true, true], [false, true]] => "𜶥",
-                [[false, true], [false, true], [true, true], [false, true]] => "𜶦",
-                [[true, true], [false, true], [true, true], [false, true]] => "𜶧",
-                [[false, false], [true, true], [true, true], [false, true]] => "𜶨",
-                [[true, false], [true, true], [true, true], [false, true]] => "𜶩",
-                [[false, true], [true, true], [true, true], [false, true]] => "𜶪",
-                [[true, true], [true, true], [true, true], [false, true]] => "𜶫",
-                [[false, false], [false, false], [false, false], [true, true]] => "▂",
-                [[true, false], [false, false], [false, false], [true, true]] => "𜶬",
-                [[false, true], [false, false], [false, false], [true, true]] => "𜶭",
-                [[true, true], [false, false], [false, false], [true, true]] => "𜶮",
-                [[false, false], [true, false], [false, false], [true, true]] => "𜶯",
-                [[true, false], [true, false], [false, false], [true, true]] => "𜶰",
-                [[false, true], [true, false], [false, false], [true, true]] => "𜶱",
-                [[true, true], [true, false], [false, false], [true, true]] => "𜶲",
-                [[false, false], [false, true], [false, false], [true, true]] => "𜶳",
-                [[true, false], [false, true], [false, false], [true, true]] => "𜶴",
-                [[false, true], [false, true], [false, false], [true, true]] => "𜶵",
-                [[true, true], [false, true], [false, false], [true, true]] => "𜶶",
-                [[false, false], [true, true], [false, false], [true, true]] => "𜶷",
-                [[true, false], [true, true], [false, false], [true, true]] => "𜶸",
-                [[false, true], [true, true], [false, false], [true, true]] => "𜶹",
-                [[true, true], [true, true], [false, false], [true, true]] => "𜶺",
-                [[false, false], [false, false], [true, false], [true, true]] => "𜶻",
-                [[true, false], [false, false], [true, false], [true, true]] => "𜶼",
-                [[false, true], [false, false], [true, false], [true, true]] => "𜶽",
-                [[true, true], [false, false], [true, false], [true, true]] => "𜶾",
-                [[false, false], [true, false], [true, false], [true, true]] => "𜶿",
-                [[true, false], [true, false], [true, false], [true, true]] => "𜷀",
-                [[false, true], [true, false], [true, false], [true, true]] => "𜷁",
-                [[true, true], [true, false], [true, false], [true, true]] => "𜷂",
-                [[false, false], [false, true], [true, false], [true, true]] => "𜷃",
-                [[true, false], [false, true], [true, false], [true, true]] => "𜷄",
-                [[false, true], [false, true], [true, false], [true, true]] => "𜷅",
-                [[true, true], [false, true], [true, false], [true, true]] => "𜷆",
-                [[false, false], [true, true], [true, false], [true, true]] => "𜷇",
-                [[true, false], [true, true], [true, false], [true, true]] => "𜷈",
-                [[false, true], [true, true], [true, false], [true, true]] => "𜷉",
-                [[true, true], [true, true], [true, false], [true, true]] => "𜷊",
-                [[false, false], [false, false], [false, true], [true, true]] => "𜷋",
-                [[true, false], [false, false], [false, true], [true, true]] => "𜷌",
-                [[false, true], [false, false], [false, true], [true, true]] => "𜷍",
-                [[true, true], [false, false], [false, true], [true, true]] => "𜷎",
-                [[false, false], [true, false], [false, true], [true, true]] => "𜷏",
-                [[true, false], [true, false], [false, true], [true, true]] => "𜷐",
-                [[false, true], [true, false], [false, true], [true, true]] => "𜷑",
-                [[true, true], [true, false], [false, true], [true, true]] => "𜷒",
-                [[false, false], [false, true], [false, true], [true, true]] => "𜷓",
-                [[true, false], [false, true], [false, true], [true, true]] => "𜷔",
-                [[false, true], [false, true], [false, true], [true, true]] => "𜷕",
-                [[true, true], [false, true], [false, true], [true, true]] => "𜷖",
-                [[false, false], [true, true], [false, true], [true, true]] => "𜷗",
-                [[true, false], [true, true], [false, true], [true, true]] => "𜷘",
-                [[false, true], [true, true], [false, true], [true, true]] => "𜷙",
-                [[true, true], [true, true], [false, true], [true, true]] => "𜷚",
-                [[false, false], [false, false], [true, true], [true, true]] => "▄",
-                [[true, false], [false, false], [true, true], [true, true]] => "𜷛",
-                [[false, true], [false, false], [true, true], [true, true]] => "𜷜",
-                [[true, true], [false, false], [true, true], [true, true]] => "𜷝",
-                [[false, false], [true, false], [true, true], [true, true]] => "𜷞",
-                [[true, false], [true, false], [true, true], [true, true]] => "▙",
-                [[false, true], [true, false], [true, true], [true, true]] => "𜷟",
-                [[true, true], [true, false], [true, true], [true, true]] => "𜷠",
-                [[false, false], [false, true], [true, true], [true, true]] => "𜷡",
-                [[true, false], [false, true], [true, true], [true, true]] => "𜷢",
-                [[false, true], [false, true], [true, true], [true, true]] => "▟",
-                [[true, true], [false, true], [true, true], [true, true]] => "𜷣",
-                [[false, false], [true, true], [true, true], [true, true]] => "▆",
-                [[true, false], [true, true], [true, true], [true, true]] => "𜷤",
-                [[false, true], [true, true], [true, true], [true, true]] => "𜷥",
-                [[true, true], [true, true], [true, true], [true, true]] => "█",
-            },
+impl GlyphSet<4> for Octants {
+    #[rustfmt::skip]
+    fn glyph(dots: [[bool; glyphs::WIDTH]; 4]) -> char {
+        match dots {
+            [[false, false], [false, false], [false, false], [false, false]] => ' ',
+            [[true, false], [false, false], [false, false], [false, false]] => '𜺨',
+            [[false, true], [false, false], [false, false], [false, false]] => '𜺫',
+            [[true, true], [false, false], [false, false], [false, false]] => '🮂',
+            [[false, false], [true, false], [false, false], [false, false]] => '𜴀',
+            [[true, false], [true, false], [false, false], [false, false]] => '▘',
+            [[false, true], [true, false], [false, false], [false, false]] => '𜴁',
+            [[true, true], [true, false], [false, false], [false, false]] => '𜴂',
+            [[false, false], [false, true], [false, false], [false, false]] => '𜴃',
+            [[true, false], [false, true], [false, false], [false, false]] => '𜴄',
+            [[false, true], [false, true], [false, false], [false, false]] => '▝',
+            [[true, true], [false, true], [false, false], [false, false]] => '𜴅',
+            [[false, false], [true, true], [false, false], [false, false]] => '𜴆',
+            [[true, false], [true, true], [false, false], [false, false]] => '𜴇',
+            [[false, true], [true, true], [false, false], [false, false]] => '𜴈',
+            [[true, true], [true, true], [false, false], [false, false]] => '▀',
+            [[false, false], [false, false], [true, false], [false, false]] => '𜴉',
+            [[true, false], [false, false], [true, false], [false, false]] => '𜴊',
+            [[false, true], [false, false], [true, false], [false, false]] => '𜴋',
+            [[true, true], [false, false], [true, false], [false, false]] => '𜴌',
+            [[false, false], [true, false], [true, false], [false, false]] => '🯦',
+            [[true, false], [true, false], [true, false], [false, false]] => '𜴍',
+            [[false, true], [true, false], [true, false], [false, false]] => '𜴎',
+            [[true, true], [true, false], [true, false], [false, false]] => '𜴏',
+            [[false, false], [false, true], [true, false], [false, false]] => '𜴐',
+            [[true, false], [false, true], [true, false], [false, false]] => '𜴑',
+            [[false, true], [false, true], [true, false], [false, false]] => '𜴒',
+            [[true, true], [false, true], [true, false], [false, false]] => '𜴓',
+            [[false, false], [true, true], [true, false], [false, false]] => '𜴔',
+            [[true, false], [true, true], [true, false], [false, false]] => '𜴕',
+            [[false, true], [true, true], [true, false], [false, false]] => '𜴖',
+            [[true, true], [true, true], [true, false], [false, false]] => '𜴗',
+            [[false, false], [false, false], [false, true], [false, false]] => '𜴘',
+            [[true, false], [false, false], [false, true], [false, false]] => '𜴙',
+            [[false, true], [false, false], [false, true], [false, false]] => '𜴚',
+            [[true, true], [false, false], [false, true], [false, false]] => '𜴛',
+            [[false, false], [true, false], [false, true], [false, false]] => '𜴜',
+            [[true, false], [true, false], [false, true], [false, false]] => '𜴝',
+            [[false, true], [true, false], [false, true], [false, false]] => '𜴞',
+            [[true, true], [true, false], [false, true], [false, false]] => '𜴟',
+            [[false, false], [false, true], [false, true], [false, false]] => '🯧',
+            [[true, false], [false, true], [false, true], [false, false]] => '𜴠',
+            [[false, true], [false, true], [false, true], [false, false]] => '𜴡',
+            [[true, true], [false, true], [false, true], [false, false]] => '𜴢',
+            [[false, false], [true, true], [false, true], [false, false]] => '𜴣',
+            [[true, false], [true, true], [false, true], [false, false]] => '𜴤',
+            [[false, true], [true, true], [false, true], [false, false]] => '𜴥',
+            [[true, true], [true, true], [false, true], [false, false]] => '𜴦',
+            [[false, false], [false, false], [true, true], [false, false]] => '𜴧',
+            [[true, false], [false, false], [true, true], [false, false]] => '𜴨',
+            [[false, true], [false, false], [true, true], [false, false]] => '𜴩',
+            [[true, true], [false, false], [true, true], [false, false]] => '𜴪',
+            [[false, false], [true, false], [true, true], [false, false]] => '𜴫',
+            [[true, false], [true, false], [true, true], [false, false]] => '𜴬',
+            [[false, true], [true, false], [true, true], [false, false]] => '𜴭',
+            [[true, true], [true, false], [true, true], [false, false]] => '𜴮',
+            [[false, false], [false, true], [true, true], [false, false]] => '𜴯',
+            [[true, false], [false, true], [true, true], [false, false]] => '𜴰',
+            [[false, true], [false, true], [true, true], [false, false]] => '𜴱',
+            [[true, true], [false, true], [true, true], [false, false]] => '𜴲',
+            [[false, false], [true, true], [true, true], [false, false]] => '𜴳',
+            [[true, false], [true, true], [true, true], [false, false]] => '𜴴',
+            [[false, true], [true, true], [true, true], [false, false]] => '𜴵',
+            [[true, true], [true, true], [true, true], [false, false]] => '🮅',
+            [[false, false], [false, false], [false, false], [true, false]] => '𜺣',
+            [[true, false], [false, false], [false, false], [true, false]] => '𜴶',
+            [[false, true], [false, false], [false, false], [true, false]] => '𜴷',
+            [[true, true], [false, false], [false, false], [true, false]] => '𜴸',
+            [[false, false], [true, false], [false, false], [true, false]] => '𜴹',
+            [[true, false], [true, false], [false, false], [true, false]] => '𜴺',
+            [[false, true], [true, false], [false, false], [true, false]] => '𜴻',
+            [[true, true], [true, false], [false, false], [true, false]] => '𜴼',
+            [[false, false], [false, true], [false, false], [true, false]] => '𜴽',
+            [[true, false], [false, true], [false, false], [true, false]] => '𜴾',
+            [[false, true], [false, true], [false, false], [true, false]] => '𜴿',
+            [[true, true], [false, true], [false, false], [true, false]] => '𜵀',
+            [[false, false], [true, true], [false, false], [true, false]] => '𜵁',
+            [[true, false], [true, true], [false, false], [true, false]] => '𜵂',
+            [[false, true], [true, true], [false, false], [true, false]] => '𜵃',
+            [[true, true], [true, true], [false, false], [true, false]] => '𜵄',
+            [[false, false], [false, false], [true, false], [true, false]] => '▖',
+            [[true, false], [false, false], [true, false], [true, false]] => '𜵅',
+            [[false, true], [false, false], [true, false], [true, false]] => '𜵆',
+            [[true, true], [false, false], [true, false], [true, false]] => '𜵇',
+            [[false, false], [true, false], [true, false], [true, false]] => '𜵈',
+            [[true, false], [true, false], [true, false], [true, false]] => '▌',
+            [[false, true], [true, false], [true, false], [true, false]] => '𜵉',
+            [[true, true], [true, false], [true, false], [true, false]] => '𜵊',
+            [[false, false], [false, true], [true, false], [true, false]] => '𜵋',
+            [[true, false], [false, true], [true, false], [true, false]] => '𜵌',
+            [[false, true], [false, true], [true, false], [true, false]] => '▞',
+            [[true, true], [false, true], [true, false], [true, false]] => '𜵍',
+            [[false, false], [true, true], [true, false], [true, false]] => '𜵎',
+            [[true, false], [true, true], [true, false], [true, false]] => '𜵏',
+            [[false, true], [true, true], [true, false], [true, false]] => '𜵐',
+            [[true, true], [true, true], [true, false], [true, false]] => '▛',
+            [[false, false], [false, false], [false, true], [true, false]] => '𜵑',
+            [[true, false], [false, false], [false, true], [true, false]] => '𜵒',
+            [[false, true], [false, false], [false, true], [true, false]] => '𜵓',
+            [[true, true], [false, false], [false, true], [true, false]] => '𜵔',
+            [[false, false], [true, false], [false, true], [true, false]] => '𜵕',
+            [[true, false], [true, false], [false, true], [true, false]] => '𜵖',
+            [[false, true], [true, false], [false, true], [true, false]] => '𜵗',
+            [[true, true], [true, false], [false, true], [true, false]] => '𜵘',
+            [[false, false], [false, true], [false, true], [true, false]] => '𜵙',
+            [[true, false], [false, true], [false, true], [true, false]] => '𜵚',
+            [[false, true], [false, true], [false, true], [true, false]] => '𜵛',
+            [[true, true], [false, true], [false, true], [true, false]] => '𜵜',
+            [[false, false], [true, true], [false, true], [true, false]] => '𜵝',
+            [[true, false], [true, true], [false, true], [true, false]] => '𜵞',
+            [[false, true], [true, true], [false, true], [true, false]] => '𜵟',
+            [[true, true], [true, true], [false, true], [true, false]] => '𜵠',
+            [[false, false], [false, false], [true, true], [true, false]] => '𜵡',
+            [[true, false], [false, false], [true, true], [true, false]] => '𜵢',
+            [[false, true], [false, false], [true, true], [true, false]] => '𜵣',
+            [[true, true], [false, false], [true, true], [true, false]] => '𜵤',
+            [[false, false], [true, false], [true, true], [true, false]] => '𜵥',
+            [[true, false], [true, false], [true, true], [true, false]] => '𜵦',
+            [[false, true], [true, false], [true, true], [true, false]] => '𜵧',
+            [[true, true], [true, false], [true, true], [true, false]] => '𜵨',
+            [[false, false], [false, true], [true, true], [true, false]] => '𜵩',
+            [[true, false], [false, true], [true, true], [true, false]] => '𜵪',
+            [[false, true], [false, true], [true, true], [true, false]] => '𜵫',
+            [[true, true], [false, true], [true, true], [true, false]] => '𜵬',
+            [[false, false], [true, true], [true, true], [true, false]] => '𜵭',
+            [[true, false], [true, true], [true, true], [true, false]] => '𜵮',
+            [[false, true], [true, true], [true, true], [true, false]] => '𜵯',
+            [[true, true], [true, true], [true, true], [true, false]] => '𜵰',
+            [[false, false], [false, false], [false, false], [false, true]] => '𜺠',
+            [[true, false], [false, false], [false, false], [false, true]] => '𜵱',
+            [[false, true], [false, false], [false, false], [false, true]] => '𜵲',
+            [[true, true], [false, false], [false, false], [false, true]] => '𜵳',
+            [[false, false], [true, false], [false, false], [false, true]] => '𜵴',
+            [[true, false], [true, false], [false, false], [false, true]] => '𜵵',
+            [[false, true], [true, false], [false, false], [false, true]] => '𜵶',
+            [[true, true], [true, false], [false, false], [false, true]] => '𜵷',
+            [[false, false], [false, true], [false, false], [false, true]] => '𜵸',
+            [[true, false], [false, true], [false, false], [false, true]] => '𜵹',
+            [[false, true], [false, true], [false, false], [false, true]] => '𜵺',
+            [[true, true], [false, true], [false, false], [false, true]] => '𜵻',
+            [[false, false], [true, true], [false, false], [false, true]] => '𜵼',
+            [[true, false], [true, true], [false, false], [false, true]] => '𜵽',
+            [[false, true], [true, true], [false, false], [false, true]] => '𜵾',
+            [[true, true], [true, true], [false, false], [false, true]] => '𜵿',
+            [[false, false], [false, false], [true, false], [false, true]] => '𜶀',
+            [[true, false], [false, false], [true, false], [false, true]] => '𜶁',
+            [[false, true], [false, false], [true, false], [false, true]] => '𜶂',
+            [[true, true], [false, false], [true, false], [false, true]] => '𜶃',
+            [[false, false], [true, false], [true, false], [false, true]] => '𜶄',
+            [[true, false], [true, false], [true, false], [false, true]] => '𜶅',
+            [[false, true], [true, false], [true, false], [false, true]] => '𜶆',
+            [[true, true], [true, false], [true, false], [false, true]] => '𜶇',
+            [[false, false], [false, true], [true, false], [false, true]] => '𜶈',
+            [[true, false], [false, true], [true, false], [false, true]] => '𜶉',
+            [[false, true], [false, true], [true, false], [false, true]] => '𜶊',
+            [[true, true], [false, true], [true, false], [false, true]] => '𜶋',
+            [[false, false], [true, true], [true, false], [false, true]] => '𜶌',
+            [[true, false], [true, true], [true, false], [false, true]] => '𜶍',
+            [[false, true], [true, true], [true, false], [false, true]] => '𜶎',
+            [[true, true], [true, true], [true, false], [false, true]] => '𜶏',
+            [[false, false], [false, false], [false, true], [false, true]] => '▗',
+            [[true, false], [false, false], [false, true], [false, true]] => '𜶐',
+            [[false, true], [false, false], [false, true], [false, true]] => '𜶑',
+            [[true, true], [false, false], [false, true], [false, true]] => '𜶒',
+            [[false, false], [true, false], [false, true], [false, true]] => '𜶓',
+            [[true, false], [true, false], [false, true], [false, true]] => '▚',
+            [[false, true], [true, false], [false, true], [false, true]] => '𜶔',
+            [[true, true], [true, false], [false, true], [false, true]] => '𜶕',
+            [[false, false], [false, true], [false, true], [false, true]] => '𜶖',
+            [[true, false], [false, true], [false, true], [false, true]] => '𜶗',
+            [[false, true], [false, true], [false, true], [false, true]] => '▐',
+            [[true, true], [false, true], [false, true], [false, true]] => '𜶘',
+            [[false, false], [true, true], [false, true], [false, true]] => '𜶙',
+            [[true, false], [true, true], [false, true], [false, true]] => '𜶚',
+            [[false, true], [true, true], [false, true], [false, true]] => '𜶛',
+            [[true, true], [true, true], [false, true], [false, true]] => '▜',
+            [[false, false], [false, false], [true, true], [false, true]] => '𜶜',
+            [[true, false], [false, false], [true, true], [false, true]] => '𜶝',
+            [[false, true], [false, false], [true, true], [false, true]] => '𜶞',
+            [[true, true], [false, false], [true, true], [false, true]] => '𜶟',
+            [[false, false], [true, false], [true, true], [false, true]] => '𜶠',
+            [[true, false], [true, false], [true, true], [false, true]] => '𜶡',
+            [[false, true], [true, false], [true, true], [false, true]] => '𜶢',
+            [[true, true], [true, false], [true, true], [false, true]] => '𜶣',
+            [[false, false], [false, true], [true, true], [false, true]] => '𜶤',
+            [[true, false], [false, true], [true, true], [false, true]] => '𜶥',
+            [[false, true], [false, true], [true, true], [false, true]] => '𜶦',
+            [[true, true], [false, true], [true, true], [false, true]] => '𜶧',
+            [[false, false], [true, true], [true, true], [false, true]] => '𜶨',
+            [[true, false], [true, true], [true, true], [false, true]] => '𜶩',
+            [[false, true], [true, true], [true, true], [false, true]] => '𜶪',
+            [[true, true], [true, true], [true, true], [false, true]] => '𜶫',
+            [[false, false], [false, false], [false, false], [true, true]] => '▂',
+            [[true, false], [false, false], [false, false], [true, true]] => '𜶬',
+            [[false, true], [false, false], [false, false], [true, true]] => '𜶭',
+            [[true, true], [false, false], [false, false], [true, true]] => '𜶮',
+            [[false, false], [true, false], [false, false], [true, true]] => '𜶯',
+            [[true, false], [true, false], [false, false], [true, true]] => '𜶰',
+            [[false, true], [true, false], [false, false], [true, true]] => '𜶱',
+            [[true, true], [true, false], [false, false], [true, true]] => '𜶲',
+            [[false, false], [false, true], [false, false], [true, true]] => '𜶳',
+            [[true, false], [false, true], [false, false], [true, true]] => '𜶴',
+            [[false, true], [false, true], [false, false], [true, true]] => '𜶵',
+            [[true, true], [false, true], [false, false], [true, true]] => '𜶶',
+            [[false, false], [true, true], [false, false], [true, true]] => '𜶷',
+            [[true, false], [true, true], [false, false], [true, true]] => '𜶸',
+            [[false, true], [true, true], [false, false], [true, true]] => '𜶹',
+            [[true, true], [true, true], [false, false], [true, true]] => '𜶺',
+            [[false, false], [false, false], [true, false], [true, true]] => '𜶻',
+            [[true, false], [false, false], [true, false], [true, true]] => '𜶼',
+            [[false, true], [false, false], [true, false], [true, true]] => '𜶽',
+            [[true, true], [false, false], [true, false], [true, true]] => '𜶾',
+            [[false, false], [true, false], [true, false], [true, true]] => '𜶿',
+            [[true, false], [true, false], [true, false], [true, true]] => '𜷀',
+            [[false, true], [true, false], [true, false], [true, true]] => '𜷁',
+            [[true, true], [true, false], [true, false], [true, true]] => '𜷂',
+            [[false, false], [false, true], [true, false], [true, true]] => '𜷃',
+            [[true, false], [false, true], [true, false], [true, true]] => '𜷄',
+            [[false, true], [false, true], [true, false], [true, true]] => '𜷅',
+            [[true, true], [false, true], [true, false], [true, true]] => '𜷆',
+            [[false, false], [true, true], [true, false], [true, true]] => '𜷇',
+            [[true, false], [true, true], [true, false], [true, true]] => '𜷈',
+            [[false, true], [true, true], [true, false], [true, true]] => '𜷉',
+            [[true, true], [true, true], [true, false], [true, true]] => '𜷊',
+            [[false, false], [false, false], [false, true], [true, true]] => '𜷋',
+            [[true, false], [false, false], [false, true], [true, true]] => '𜷌',
+            [[false, true], [false, false], [false, true], [true, true]] => '𜷍',
+            [[true, true], [false, false], [false, true], [true, true]] => '𜷎',
+            [[false, false], [true, false], [false, true], [true, true]] => '𜷏',
+            [[true, false], [true, false], [false, true], [true, true]] => '𜷐',
+            [[false, true], [true, false], [false, true], [true, true]] => '𜷑',
+            [[true, true], [true, false], [false, true], [true, true]] => '𜷒',
+            [[false, false], [false, true], [false, true], [true, true]] => '𜷓',
+            [[true, false], [false, true], [false, true], [true, true]] => '𜷔',
+            [[false, true], [false, true], [false, true], [true, true]] => '𜷕',
+            [[true, true], [false, true], [false, true], [true, true]] => '𜷖',
+            [[false, false], [true, true], [false, true], [true, true]] => '𜷗',
+            [[true, false], [true, true], [false, true], [true, true]] => '𜷘',
+            [[false, true], [true, true], [false, true], [true, true]] => '𜷙',
+            [[true, true], [true, true], [false, true], [true, true]] => '𜷚',
+            [[false, false], [false, false], [true, true], [true, true]] => '▄',
+            [[true, false], [false, false], [true, true], [true, true]] => '𜷛',
+            [[false, true], [false, false], [true, true], [true, true]] => '𜷜',
+            [[true, true], [false, false], [true, true], [true, true]] => '𜷝',
+            [[false, false], [true, false], [true, true], [true, true]] => '𜷞',
+            [[true, false], [true, false], [true, true], [true, true]] => '▙',
+            [[false, true], [true, false], [true, true], [true, true]] => '𜷟',
+            [[true, true], [true, false], [true, true], [true, true]] => '𜷠',
+            [[false, false], [false, true], [true, true], [true, true]] => '𜷡',
+            [[true, false], [false, true], [true, true], [true, true]] => '𜷢',
+            [[false, true], [false, true], [true, true], [true, true]] => '▟',
+            [[true, true], [false, true], [true, true], [true, true]] => '𜷣',
+            [[false, false], [true, true], [true, true], [true, true]] => '▆',
+            [[true, false], [true, true], [true, true], [true, true]] => '𜷤',
+            [[false, true], [true, true], [true, true], [true, true]] => '𜷥',
+            [[true, true], [true, true], [true, true], [true, true]] => '█',
         }
     }
-
-    pub fn as_str(&self) -> &'static str {
-        self.inner
-    }
 }
 
-impl fmt::Display for Char {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
-    }
-}
+pub type Columns = glyphs::Columns<Octants, 4>;
+pub type Lines = glyphs::Lines<Octants, 4>;
@@ -1,93 +1,79 @@
-mod columns;
-mod lines;
+use crate::graph::glyphs::{self, GlyphSet};
 
-pub use columns::Columns;
-pub use lines::Lines;
-use std::fmt;
+/// Sextants, 2 dots wide and 3 tall
+#[derive(Debug)]
+pub struct Sextants;
 
-struct Char {
-    inner: &'static str,
-}
-
-impl Char {
-    pub fn new(dots: [[bool; 2]; 3]) -> Self {
-        Self {
-            inner: match dots {
-                [[false, false], [false, false], [false, false]] => " ",
-                [[true, false], [false, false], [false, false]] => "🬀",
-                [[false, true], [false, false], [false, false]] => "🬁",
-                [[true, true], [false, false], [false, false]] => "🬂",
-                [[false, false], [true, false], [false, false]] => "🬃",
-                [[true, false], [true, false], [false, false]] => "🬄",
-                [[false, true], [true, false], [false, false]] => "🬅",
-                [[true, true], [true, false], [false, false]] => "🬆",
-                [[false, false], [false, true], [false, false]] => "🬇",
-                [[true, false], [false, true], [false, false]] => "🬈",
-                [[false, true], [false, true], [false, false]] => "🬉",
-                [[true, true], [false, true], [false, false]] => "🬊",
-                [[false, false], [true, true], [false, false]] => "🬋",
-                [[true, false], [true, true], [false, false]] => "🬌",
-                [[false, true], [true, true], [false, false]] => "🬍",
-                [[true, true], [true, true], [false, false]] => "🬎",
-                [[false, false], [false, false], [true, false]] => "🬏",
-                [[true, false], [false, false], [true, false]] => "🬐",
-                [[false, true], [false, false], [true, false]] => "🬑",
-                [[true, true], [false, false], [true, false]] => "🬒",
-                [[false, false], [true, false], [true, false]] => "🬓",
-                [[true, false], [true, false], [true, false]] => "▌",
-                [[false, true], [true, false], [true, false]] => "🬔",
-                [[true, true], [true, false], [true, false]] => "🬕",
-                [[false, false], [false, true], [true, false]] => "🬖",
-                [[true, false], [false, true], [true, false]] => "🬗",
-                [[false, true], [false, true], [true, false]] => "🬘",
-                [[true, true], [false, true], [true, false]] => "🬙",
-                [[false, false], [true, true], [true, false]] => "🬚",
-                [[true, false], [true, true], [true, false]] => "🬛",
-                [[false, true], [true, true], [true, false]] => "🬜",
-                [[true, true], [true, true], [true, false]] => "🬝",
-                [[false, false], [false, false], [false, true]] => "🬞",
-                [[true, false], [false, false], [false, true]] => "🬟",
-                [[false, true], [false, false], [false, true]] => "🬠",
-                [[true, true], [false, false], [false, true]] => "🬡",
-                [[false, false], [true, false], [false, true]] => "🬢",
-                [[true, false], [true, false], [false, true]] => "🬣",
-                [[false, true], [true, false], [false, true]] => "🬤",
-                [[true, true], [true, false], [false, true]] => "🬥",
-                [[false, false], [false, true], [false, true]] => "🬦",
-                [[true, false], [false, true], [false, true]] => "🬧",
-                [[false, true], [false, true], [false, true]] => "▐",
-                [[true, true], [false, true], [false, true]] => "🬨",
-                [[false, false], [true, true], [false, true]] => "🬩",
-                [[true, false], [true, true], [false, true]] => "🬪",
-                [[false, true], [true, true], [false, true]] => "🬫",
-                [[true, true], [true, true], [false, true]] => "🬬",
-                [[false, false], [false, false], [true, true]] => "🬭",
-                [[true, false], [false, false], [true, true]] => "🬮",
-                [[false, true], [false, false], [true, true]] => "🬯",
-                [[true, true], [false, false], [true, true]] => "🬰",
-                [[false, false], [true, false], [true, true]] => "🬱",
-                [[true, false], [true, false], [true, true]] => "🬲",
-                [[false, true], [true, false], [true, true]] => "🬳",
-                [[true, true], [true, false], [true, true]] => "🬴",
-                [[false, false], [false, true], [true, true]] => "🬵",
-                [[true, false], [false, true], [true, true]] => "🬶",
-                [[false, true], [false, true], [true, true]] => "🬷",
-                [[true, true], [false, true], [true, true]] => "🬸",
-                [[false, false], [true, true], [true, true]] => "🬹",
-                [[true, false], [true, true], [true, true]] => "🬺",
-                [[false, true], [true, true], [true, true]] => "🬻",
-                [[true, true], [true, true], [true, true]] => "█",
-            },
+impl GlyphSet<3> for Sextants {
+    fn glyph(dots: [[bool; glyphs::WIDTH]; 3]) -> char {
+        match dots {
+            [[false, false], [false, false], [false, false]] => ' ',
+            [[true, false], [false, false], [false, false]] => '🬀',
+            [[false, true], [false, false], [false, false]] => '🬁',
+            [[true, true], [false, false], [false, false]] => '🬂',
+            [[false, false], [true, false], [false, false]] => '🬃',
+            [[true, false], [true, false], [false, false]] => '🬄',
+            [[false, true], [true, false], [false, false]] => '🬅',
+            [[true, true], [true, false], [false, false]] => '🬆',
+            [[false, false], [false, true], [false, false]] => '🬇',
+            [[true, false], [false, true], [false, false]] => '🬈',
+            [[false, true], [false, true], [false, false]] => '🬉',
+            [[true, true], [false, true], [false, false]] => '🬊',
+            [[false, false], [true, true], [false, false]] => '🬋',
+            [[true, false], [true, true], [false, false]] => '🬌',
+            [[false, true], [true, true], [false, false]] => '🬍',
+            [[true, true], [true, true], [false, false]] => '🬎',
+            [[false, false], [false, false], [true, false]] => '🬏',
+            [[true, false], [false, false], [true, false]] => '🬐',
+            [[false, true], [false, false], [true, false]] => '🬑',
+            [[true, true], [false, false], [true, false]] => '🬒',
+            [[false, false], [true, false], [true, false]] => '🬓',
+            [[true, false], [true, false], [true, false]] => '▌',
+            [[false, true], [true, false], [true, false]] => '🬔',
+            [[true, true], [true, false], [true, false]] => '🬕',
+            [[false, false], [false, true], [true, false]] => '🬖',
+            [[true, false], [false, true], [true, false]] => '🬗',
+            [[false, true], [false, true], [true, false]] => '🬘',
+            [[true, true], [false, true], [true, false]] => '🬙',
+            [[false, false], [true, true], [true, false]] => '🬚',
+            [[true, false], [true, true], [true, false]] => '🬛',
+            [[false, true], [true, true], [true, false]] => '🬜',
+            [[true, true], [true, true], [true, false]] => '🬝',
+            [[false, false], [false, false], [false, true]] => '🬞',
+            [[true, false], [false, false], [false, true]] => '🬟',
+            [[false, true], [false, false], [false, true]] => '🬠',
+            [[true, true], [false, false], [false, true]] => '🬡',
+            [[false, false], [true, false], [false, true]] => '🬢',
+            [[true, false], [true, false], [false, true]] => '🬣',
+            [[false, true], [true, false], [false, true]] => '🬤',
+            [[true, true], [true, false], [false, true]] => '🬥',
+            [[false, false], [false, true], [false, true]] => '🬦',
+            [[true, false], [false, true], [false, true]] => '🬧',
+            [[false, true], [false, true], [false, true]] => '▐',
+            [[true, true], [false, true], [false, true]] => '🬨',
+            [[false, false], [true, true], [false, true]] => '🬩',
+            [[true, false], [true, true], [false, true]] => '🬪',
+            [[false, true], [true, true], [false, true]] => '🬫',
+            [[true, true], [true, true], [false, true]] => '🬬',
+            [[false, false], [false, false], [true, true]] => '🬭',
+            [[true, false], [false, false], [true, true]] => '🬮',
+            [[false, true], [false, false], [true, true]] => '🬯',
+            [[true, true], [false, false], [true, true]] => '🬰',
+            [[false, false], [true, false], [true, true]] => '🬱',
+            [[true, false], [true, false], [true, true]] => '🬲',
+            [[false, true], [true, false], [true, true]] => '🬳',
+            [[true, true], [true, false], [true, true]] => '🬴',
+            [[false, false], [false, true], [true, true]] => '🬵',
+            [[true, false], [false, true], [true, true]] => '🬶',
+            [[false, true], [false, true], [true, true]] => '🬷',
+            [[true, true], [false, true], [true, true]] => '🬸',
+            [[false, false], [true, true], [true, true]] => '🬹',
+            [[true, false], [true, true], [true, true]] => '🬺',
+            [[false, true], [true, true], [true, true]] => '🬻',
+            [[true, true], [true, true], [true, true]] => '█',
         }
     }
-
-    pub fn as_str(&self) -> &'static str {
-        self.inner
-    }
 }
 
-impl fmt::Display for Char {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        self.inner.fmt(f)
-    }
-}
+pub type Columns = glyphs::Columns<Sextants, 3>;
+pub type Lines = glyphs::Lines<Sextants, 3>;
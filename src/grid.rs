@@ -1,12 +1,14 @@
 mod bounds;
+mod expression;
 
-use crate::opt::CharType;
+use crate::InputLine;
+use crate::annotate::{self, Extremes, Mark};
+use crate::canvas::{Canvas, Glyphs};
+use crate::input::{self, LineParseError, ParseLine};
+use crate::opt::{AngleUnit, CharType, OnError};
 use crate::util;
-use bounds::{CartesianBound, CartesianBounds};
-use braillefb::{Framebuffer, FramebufferStyle};
-// use std::collections::HashMap;
-use std::collections::HashSet;
-// use std::collections::BTreeSet as HashSet;
+use bounds::{CartesianBound, CartesianBounds, CartesianBoundsBuilder};
+pub use expression::Expression;
 use std::io::{LineWriter, Write};
 
 type DotUnit = u16;
@@ -80,76 +82,92 @@ impl IntoIterator for CartesianPoints {
     }
 }
 
+/// The dots of a grid, with `y` counting up from the bottom
 struct GridDots {
     width: DotUnit,
     height: DotUnit,
-    inner: HashSet<Dot>,
+    glyphs: Glyphs,
+    /// The dots, packed into bits and printed from the top down
+    canvas: Canvas,
 }
 
 impl GridDots {
-    pub fn new(width: DotUnit, height: DotUnit, capacity: usize) -> Self {
-        // let inner = (0..height)
-        //     .flat_map(move |y| (0..width).map(move |x| (Dot::new(x, y), false)))
-        //     .collect::<HashMap<Dot, bool>>();
-        // let inner = HashSet::with_capacity(usize::from(width) * usize::from(height));
-        // let inner = HashSet::new();
-        let inner = HashSet::with_capacity(capacity);
-
-        // debug_assert_eq!(usize::try_from(width * height).unwrap(), inner.len());
-
-        Self {
+    pub fn try_new(width: DotUnit, height: DotUnit, glyphs: Glyphs) -> anyhow::Result<Self> {
+        Ok(Self {
             width,
             height,
-            inner,
-        }
+            glyphs,
+            canvas: Canvas::try_new(width.into(), height.into())?,
+        })
     }
 
     pub fn merge_points(&mut self, points: &CartesianPoints) {
         for point in points {
-            #[allow(
-                clippy::cast_possible_truncation,
-                clippy::cast_sign_loss,
-                clippy::cast_precision_loss
-            )]
-            let x = util::scale(
-                point.x,
-                points.bounds.x.min,
-                points.bounds.x.max,
-                0.,
-                f64::from(self.width - 1),
-            )
-            .round() as DotUnit;
-
-            #[allow(
-                clippy::cast_possible_truncation,
-                clippy::cast_sign_loss,
-                clippy::cast_precision_loss
-            )]
-            let y = util::scale(
-                point.y,
-                points.bounds.y.min,
-                points.bounds.y.max,
-                0.,
-                f64::from(self.height - 1),
-            )
-            .round() as DotUnit;
-
-            let dot = Dot::new(x, y);
-            // self.inner.entry(dand_modify).or_insert(|e| *e = true);
-            self.inner.insert(dot);
+            self.insert(point, &points.bounds);
         }
     }
 
-    pub fn into_dots(self) -> Vec<bool> {
-        let mut dots = Vec::with_capacity(usize::from(self.width * self.height));
-        for y in (0..self.height).rev() {
-            for x in 0..self.width {
-                let dot = Dot::new(x, y);
-                dots.push(self.inner.contains(&dot));
-            }
+    /// Set the dot a point is drawn at, unless it's outside the bounds
+    pub fn insert(&mut self, point: &Point, bounds: &CartesianBounds) {
+        if bounds.contains(point) {
+            let dot = self.dot(point, bounds);
+            self.canvas
+                .set(i32::from(dot.x), i32::from(self.height - 1 - dot.y));
         }
+    }
 
-        dots
+    /// The dot a point is drawn at
+    fn dot(&self, point: &Point, bounds: &CartesianBounds) -> Dot {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let x = util::scale(
+            point.x,
+            bounds.x.min,
+            bounds.x.max,
+            0.,
+            f64::from(self.width - 1),
+        )
+        .round() as DotUnit;
+
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let y = util::scale(
+            point.y,
+            bounds.y.min,
+            bounds.y.max,
+            0.,
+            f64::from(self.height - 1),
+        )
+        .round() as DotUnit;
+
+        Dot::new(x, y)
+    }
+
+    /// Marks for the points given, which are the extremes of every point drawn in the grid
+    fn marks(&self, points: &[Point], bounds: &CartesianBounds) -> Vec<Mark> {
+        let (cell_width, cell_height) = self.glyphs.cell_size();
+        points
+            .iter()
+            .map(|point| {
+                let dot = self.dot(point, bounds);
+                Mark {
+                    row: usize::from(self.height - 1 - dot.y) / cell_height,
+                    column: usize::from(dot.x) / cell_width,
+                    label: format!("{},{}", point.x, point.y),
+                }
+            })
+            .collect()
+    }
+
+    /// Print the grid, with a newline after each row of characters
+    pub fn render(&self) -> String {
+        self.canvas.render(self.glyphs)
     }
 }
 
@@ -163,6 +181,16 @@ impl Point {
     fn new(x: f64, y: f64) -> Self {
         Self { x, y }
     }
+
+    /// The point at an angle and distance from the origin
+    fn from_polar(theta: f64, r: f64, unit: AngleUnit) -> Self {
+        let theta = match unit {
+            AngleUnit::Radians => theta,
+            AngleUnit::Degrees => theta.to_radians(),
+        };
+
+        Self::new(r * theta.cos(), r * theta.sin())
+    }
 }
 
 impl From<(f64, f64)> for Point {
@@ -188,18 +216,21 @@ pub fn print_graph<W: Write>(
     reader: impl std::io::BufRead,
     mut writer: LineWriter<W>,
 ) -> anyhow::Result<()> {
-    let grid_style = match opt.kind().char_type() {
-        CharType::Octant => FramebufferStyle::Octants,
-        CharType::Braille => FramebufferStyle::Braille,
-        _ => FramebufferStyle::default(),
+    let glyphs = match opt.kind().char_type() {
+        CharType::Octant => Glyphs::Octants,
+        CharType::Sextant => Glyphs::Sextants,
+        CharType::HalfBlock => Glyphs::Quadrants,
+        CharType::Braille | CharType::Block | CharType::Column => Glyphs::Braille,
     };
 
-    let grid = opt.grid.unwrap();
+    let grid = opt.grid.as_deref().unwrap();
     let (width, height) = if grid.is_empty() {
+        let (cell_width, cell_height) = glyphs.cell_size();
         let (width, height) = crate::util::get_terminal_size().map(|(w, h)| {
             (
-                DotUnit::from(w) * 2,
-                DotUnit::from(h - DotUnit::from(!opt.use_full_default_height)) * 4,
+                DotUnit::from(w) * cell_width as DotUnit,
+                DotUnit::from(h - DotUnit::from(!opt.use_full_default_height))
+                    * cell_height as DotUnit,
             )
         })?;
         let square = width.min(height);
@@ -210,58 +241,155 @@ pub fn print_graph<W: Write>(
         (grid[0], grid[1])
     };
 
+    let builder = opt
+        .grid_bounds
+        .or(opt.x_bounds)
+        .or(opt.y_bounds)
+        .is_some()
+        .then(|| bounds_builder(&opt));
+
+    // With every bound given, each point is drawn as soon as it's read instead of being kept
+    let given_bounds = builder.and_then(CartesianBoundsBuilder::build);
+    let mut grid = GridDots::try_new(width, height, glyphs)?;
+    let mut extremes = opt.annotate.then(|| Extremes::new(|point: Point| point.y));
+
+    let options = opt.read_options();
+    let first_line_number = options.line_number;
+    let format = options.format.clone();
     let mut points: Vec<Point> = vec![];
-    for line in reader.lines() {
+    for (line, line_number) in input::lines(reader, options).zip(first_line_number..) {
         let line = line?;
-        let (x, y) = line.split_once(|c: char| c.is_ascii_whitespace()).unwrap();
-        points.push(Point::new(x.parse()?, y.parse()?));
-    }
-
-    let points = if opt.grid_bounds.or(opt.x_bounds).or(opt.y_bounds).is_some() {
-        let mut builder = CartesianBounds::builder();
-
-        let (x_min, x_max) = opt
-            .x_bounds
-            .or(opt.grid_bounds)
-            .map_or((None, None), |b| (b.min(), b.max()));
-        let (y_min, y_max) = opt
-            .y_bounds
-            .or(opt.grid_bounds)
-            .map_or((None, None), |b| (b.min(), b.max()));
-
-        if let Some(x_min) = x_min {
-            builder.x_min(x_min);
-        }
-        if let Some(x_max) = x_max {
-            builder.x_max(x_max);
+        if format.is_comment(&line) || format.is_unmatched(&line) {
+            continue;
         }
-        if let Some(y_min) = y_min {
-            builder.y_min(y_min);
+
+        let values = match &opt.parametric {
+            Some(expressions) => InputLine::<Option<f64>>::parse_line(&line, &format).map(|line| {
+                line.into_inner()
+                    .map(|t| (expressions[0].eval(t), expressions[1].eval(t)))
+                    .filter(|(x, y)| x.is_finite() && y.is_finite())
+            }),
+            None => InputLine::<[Option<f64>; 2]>::parse_line(&line, &format).map(|line| {
+                let mut values = line.into_iter();
+                values.next().flatten().zip(values.next().flatten())
+            }),
+        };
+
+        match values {
+            Ok(values) => {
+                // Points without both values are left out, like gaps in other graphs
+                if let Some((x, y)) = values {
+                    let point = match opt.polar {
+                        Some(unit) => Point::from_polar(x, y, unit),
+                        None => Point::new(x, y),
+                    };
+
+                    match &given_bounds {
+                        Some(bounds) => {
+                            if let Some(extremes) = &mut extremes
+                                && bounds.contains(&point)
+                            {
+                                extremes.push(point);
+                            }
+                            grid.insert(&point, bounds);
+                        }
+                        None => points.push(point),
+                    }
+                }
+            }
+            Err(err) => match opt.on_error {
+                OnError::Fail => {
+                    return Err(LineParseError::OnLine {
+                        line: line_number,
+                        inner: Box::new(err),
+                    }
+                    .into());
+                }
+                OnError::Skip | OnError::Null => opt.skipped_lines.borrow_mut().push(line_number),
+            },
         }
-        if let Some(y_max) = y_max {
-            builder.y_max(y_max);
+    }
+
+    let bounds = match (given_bounds, builder) {
+        (Some(bounds), _) => bounds,
+        (None, builder) => {
+            let points = match builder {
+                Some(builder) => {
+                    let bounds = builder.build_from_points(&points);
+                    CartesianPoints::new_with_bounds(points, bounds)
+                }
+                None => CartesianPoints::new(points),
+            };
+            grid.merge_points(&points);
+            if let Some(extremes) = &mut extremes {
+                for point in points
+                    .inner
+                    .iter()
+                    .filter(|point| points.bounds.contains(point))
+                {
+                    extremes.push(*point);
+                }
+            }
+            points.bounds
         }
+    };
 
-        let bounds = builder.build_from_points(&points);
-        CartesianPoints::new_with_bounds(points, bounds)
+    let graph = grid.render();
+    if let Some(extremes) = extremes {
+        let marks = grid.marks(&extremes.into_points(), &bounds);
+        let lines: Vec<&str> = graph.lines().collect();
+        let width = usize::from(width).div_ceil(glyphs.cell_size().0);
+        for line in annotate::label(&lines, &marks, width) {
+            writeln!(writer, "{line}")?;
+        }
     } else {
-        CartesianPoints::new(points)
-    };
-    let mut grid = GridDots::new(width, height, points.inner.len());
-    grid.merge_points(&points);
+        write!(writer, "{graph}")?;
+    }
 
-    let dots = grid.into_dots();
-    let fb = Framebuffer::new(&dots, width.into(), height.into()).with_style(grid_style);
+    Ok(())
+}
 
-    write!(writer, "{fb}")?;
+/// The bounds given by the options, with the rest left to be found from the points
+fn bounds_builder(opt: &crate::Opt) -> CartesianBoundsBuilder {
+    let mut builder = CartesianBounds::builder();
+
+    let (x_min, x_max) = opt
+        .x_bounds
+        .or(opt.grid_bounds)
+        .map_or((None, None), |b| (b.min(), b.max()));
+    let (y_min, y_max) = opt
+        .y_bounds
+        .or(opt.grid_bounds)
+        .map_or((None, None), |b| (b.min(), b.max()));
+
+    if let Some(x_min) = x_min {
+        builder.x_min(x_min);
+    }
+    if let Some(x_max) = x_max {
+        builder.x_max(x_max);
+    }
+    if let Some(y_min) = y_min {
+        builder.y_min(y_min);
+    }
+    if let Some(y_max) = y_max {
+        builder.y_max(y_max);
+    }
 
-    Ok(())
+    builder
 }
 
 #[cfg(test)]
 mod grid_tests {
     use super::*;
 
+    /// Each dot of the grid, a row at a time from the top left
+    fn dots(grid: &GridDots) -> Vec<bool> {
+        (0..grid.height)
+            .flat_map(|y| (0..grid.width).map(move |x| (x, y)))
+            .map(|(x, y)| grid.canvas.get(i32::from(x), i32::from(y)))
+            .collect()
+    }
+
     #[test]
     fn check_coord_stuff() {
         // -- -- -- -*
@@ -273,7 +401,7 @@ mod grid_tests {
         // -- *- -- --
         // -* -- -- --
         // *- -- -- --
-        let mut grid = GridDots::new(4 * 2, 2 * 4, 8);
+        let mut grid = GridDots::try_new(4 * 2, 2 * 4, Glyphs::Braille).unwrap();
         let points = [
             Point::new(0., -3.),
             Point::new(1., -2.),
@@ -305,10 +433,9 @@ mod grid_tests {
         assert!((points.bounds.y.min - -3.).abs() < f64::EPSILON);
         assert!((points.bounds.y.max - 4.).abs() < f64::EPSILON);
 
-        let dots = grid.into_dots();
-        assert_eq!(expected, dots);
+        assert_eq!(expected, dots(&grid));
 
-        let actual_img = Framebuffer::new(&dots, 8, 8).to_string();
+        let actual_img = grid.render();
         insta::assert_snapshot!(actual_img);
     }
 
@@ -344,13 +471,10 @@ mod grid_tests {
                 1.,
             ),
         );
-        let mut grid = GridDots::new(26 * 2 - 1, 10 * 4, points.inner.len());
+        let mut grid = GridDots::try_new(26 * 2 - 1, 10 * 4, Glyphs::Braille).unwrap();
         grid.merge_points(&points);
 
-        let width = grid.width;
-        let height = grid.height;
-        let grid_values = grid.into_dots();
-        let graph = Framebuffer::new(&grid_values, usize::from(width), usize::from(height));
+        let graph = grid.render();
 
         insta::assert_snapshot!(graph);
     }
@@ -534,6 +658,49 @@ mod grid_tests {
             false, false, false, false,
         ];
 
-        insta::assert_snapshot!(Framebuffer::new(&expected, 26 * 2, 10 * 4).to_string());
+        // Each dot is a point with the same coordinates, counting up from the bottom
+        let (width, height) = (26 * 2, 10 * 4);
+        let bounds = CartesianBounds::new(0., f64::from(width - 1), 0., f64::from(height - 1));
+        let mut grid = GridDots::try_new(width, height, Glyphs::Braille).unwrap();
+        for (index, dot) in expected.iter().enumerate() {
+            if *dot {
+                let x = u16::try_from(index % usize::from(width)).unwrap();
+                let row = u16::try_from(index / usize::from(width)).unwrap();
+                grid.insert(
+                    &Point::new(f64::from(x), f64::from(height - 1 - row)),
+                    &bounds,
+                );
+            }
+        }
+
+        assert_eq!(expected, dots(&grid));
+        insta::assert_snapshot!(grid.render());
+    }
+
+    #[test]
+    fn insert_at_edges() {
+        let bounds = CartesianBounds::new(-1., 1., -1., 1.);
+        let mut grid = GridDots::try_new(8, 8, Glyphs::Braille).unwrap();
+        for point in [
+            Point::new(-1., 1.),
+            Point::new(1., 1.),
+            Point::new(-1., -1.),
+            Point::new(1., -1.),
+            Point::new(0., 0.),
+            Point::new(1.5, 0.),
+            Point::new(0., -1.5),
+        ] {
+            grid.insert(&point, &bounds);
+        }
+
+        // The corners of the bounds are the corners of the grid, and points outside are left out
+        for (x, y) in [(0, 0), (7, 0), (0, 7), (7, 7)] {
+            assert!(grid.canvas.get(x, y), "({x}, {y})");
+        }
+        // The middle rounds up to the dot right of it and above it, which is counted from the top
+        assert!(grid.canvas.get(4, 3));
+        assert_eq!(5, dots(&grid).into_iter().filter(|dot| *dot).count());
+        assert!(!grid.canvas.get(8, 4));
+        assert!(!grid.canvas.get(-1, 0));
     }
 }
@@ -118,6 +118,20 @@ impl CartesianBoundsBuilder {
         self.y_max.value = max;
     }
 
+    /// The bounds, if every one of them was given
+    pub fn build(self) -> Option<CartesianBounds> {
+        let Self {
+            x_min,
+            x_max,
+            y_min,
+            y_max,
+        } = self;
+        [x_min, x_max, y_min, y_max]
+            .iter()
+            .all(|bound| bound.provided)
+            .then(|| CartesianBounds::new(x_min.value, x_max.value, y_min.value, y_max.value))
+    }
+
     pub fn build_from_points(self, points: &[Point]) -> CartesianBounds {
         let Self {
             mut x_min,
@@ -141,6 +155,11 @@ impl CartesianBounds {
         CartesianBoundsBuilder::default()
     }
 
+    /// Whether a point is inside the bounds, including on their edges
+    pub fn contains(&self, point: &Point) -> bool {
+        (self.x.min..=self.x.max).contains(&point.x) && (self.y.min..=self.y.max).contains(&point.y)
+    }
+
     pub fn new(x_min: f64, x_max: f64, y_min: f64, y_max: f64) -> Self {
         Self {
             x: CartesianBound::new(x_min, x_max),
@@ -0,0 +1,227 @@
+//! Expressions of `t` for --parametric, like `cos(3*t)` or `t^2 - 1`.
+//!
+//! Expressions are made of numbers, `t`, the constants `pi` and `e`, the operators `+`, `-`, `*`,
+//! `/`, `%`, and `^` (which binds tightest, from right to left), parentheses, and the functions
+//! `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `sqrt`, `abs`, `exp`, `ln`, `log` (base 10),
+//! `floor`, and `ceil`.
+
+use std::str::FromStr;
+
+type Function = fn(f64) -> f64;
+
+/// The functions an expression can call, with what they do to their argument
+const FUNCTIONS: [(&str, Function); 13] = [
+    ("sin", f64::sin),
+    ("cos", f64::cos),
+    ("tan", f64::tan),
+    ("asin", f64::asin),
+    ("acos", f64::acos),
+    ("atan", f64::atan),
+    ("sqrt", f64::sqrt),
+    ("abs", f64::abs),
+    ("exp", f64::exp),
+    ("ln", f64::ln),
+    ("log", f64::log10),
+    ("floor", f64::floor),
+    ("ceil", f64::ceil),
+];
+
+/// An expression parsed once, to be evaluated for each value of `t`
+#[derive(Debug, Clone)]
+pub struct Expression(Node);
+
+#[derive(Debug, Clone)]
+enum Node {
+    Number(f64),
+    T,
+    Negate(Box<Node>),
+    Call(Function, Box<Node>),
+    Binary(char, Box<Node>, Box<Node>),
+}
+
+impl Expression {
+    /// The value of the expression at `t`
+    #[must_use]
+    pub fn eval(&self, t: f64) -> f64 {
+        self.0.eval(t)
+    }
+}
+
+impl Node {
+    fn eval(&self, t: f64) -> f64 {
+        match self {
+            Self::Number(value) => *value,
+            Self::T => t,
+            Self::Negate(node) => -node.eval(t),
+            Self::Call(function, node) => function(node.eval(t)),
+            Self::Binary(operator, left, right) => {
+                let (left, right) = (left.eval(t), right.eval(t));
+                match operator {
+                    '+' => left + right,
+                    '-' => left - right,
+                    '*' => left * right,
+                    '/' => left / right,
+                    '%' => left % right,
+                    '^' => left.powf(right),
+                    _ => unreachable!("only known operators are parsed"),
+                }
+            }
+        }
+    }
+}
+
+impl FromStr for Expression {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { s, position: 0 };
+        let node = parser.sum()?;
+
+        parser.skip_whitespace();
+        if let Some(c) = parser.peek() {
+            anyhow::bail!("Unexpected {c:?} at position {}", parser.position + 1);
+        }
+
+        Ok(Self(node))
+    }
+}
+
+/// A recursive descent parser, with one method for each level of precedence
+struct Parser<'a> {
+    s: &'a str,
+    position: usize,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        let rest = &self.s[self.position..];
+        self.position += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.s[self.position..].chars().next()
+    }
+
+    /// Move past the next character if it's one of `chars`, returning it
+    fn next_of(&mut self, chars: &[char]) -> Option<char> {
+        self.skip_whitespace();
+        let c = self.peek().filter(|c| chars.contains(c))?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn sum(&mut self) -> anyhow::Result<Node> {
+        let mut node = self.product()?;
+        while let Some(operator) = self.next_of(&['+', '-']) {
+            node = Node::Binary(operator, Box::new(node), Box::new(self.product()?));
+        }
+        Ok(node)
+    }
+
+    fn product(&mut self) -> anyhow::Result<Node> {
+        let mut node = self.unary()?;
+        while let Some(operator) = self.next_of(&['*', '/', '%']) {
+            node = Node::Binary(operator, Box::new(node), Box::new(self.unary()?));
+        }
+        Ok(node)
+    }
+
+    fn unary(&mut self) -> anyhow::Result<Node> {
+        if self.next_of(&['-']).is_some() {
+            Ok(Node::Negate(Box::new(self.unary()?)))
+        } else {
+            self.power()
+        }
+    }
+
+    fn power(&mut self) -> anyhow::Result<Node> {
+        let node = self.atom()?;
+        if self.next_of(&['^']).is_some() {
+            Ok(Node::Binary('^', Box::new(node), Box::new(self.unary()?)))
+        } else {
+            Ok(node)
+        }
+    }
+
+    fn atom(&mut self) -> anyhow::Result<Node> {
+        self.skip_whitespace();
+        let start = self.position;
+
+        if self.next_of(&['(']).is_some() {
+            let node = self.sum()?;
+            return match self.next_of(&[')']) {
+                Some(_) => Ok(node),
+                None => anyhow::bail!("Missing ')' for the '(' at position {}", start + 1),
+            };
+        }
+
+        let rest = &self.s[start..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '.' || c == '_'))
+            .unwrap_or(rest.len());
+        let token = &rest[..len];
+        self.position += len;
+
+        match token {
+            "" => match self.peek() {
+                Some(c) => anyhow::bail!("Unexpected {c:?} at position {}", start + 1),
+                None => anyhow::bail!("Expected a value at the end"),
+            },
+            "t" => Ok(Node::T),
+            "pi" => Ok(Node::Number(std::f64::consts::PI)),
+            "e" => Ok(Node::Number(std::f64::consts::E)),
+            name if name.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                let Some((_, function)) = FUNCTIONS.iter().find(|(known, _)| *known == name) else {
+                    anyhow::bail!("Unknown name {name:?} at position {}", start + 1);
+                };
+
+                if self.next_of(&['(']).is_none() {
+                    anyhow::bail!("Expected '(' after {name:?}");
+                }
+                let argument = self.sum()?;
+                if self.next_of(&[')']).is_none() {
+                    anyhow::bail!("Missing ')' after the argument to {name:?}");
+                }
+
+                Ok(Node::Call(*function, Box::new(argument)))
+            }
+            number => number.parse().map(Node::Number).map_err(|_| {
+                anyhow::anyhow!("Invalid number {number:?} at position {}", start + 1)
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(s: &str, t: f64) -> f64 {
+        s.parse::<Expression>().unwrap().eval(t)
+    }
+
+    #[test]
+    fn precedence() {
+        assert!((eval("1 + 2 * 3", 0.) - 7.).abs() < f64::EPSILON);
+        assert!((eval("(1 + 2) * 3", 0.) - 9.).abs() < f64::EPSILON);
+        assert!((eval("-t^2", 3.) - -9.).abs() < f64::EPSILON);
+        assert!((eval("2^3^2", 0.) - 512.).abs() < f64::EPSILON);
+        assert!((eval("10 - 4 - 3", 0.) - 3.).abs() < f64::EPSILON);
+        assert!((eval("2*t % 5", 4.) - 3.).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn functions_and_constants() {
+        assert!((eval("cos(pi)", 0.) - -1.).abs() < f64::EPSILON);
+        assert!((eval("sqrt(abs(t))", -16.) - 4.).abs() < f64::EPSILON);
+        assert!((eval("ln(e)", 0.) - 1.).abs() < f64::EPSILON);
+        assert!((eval("sin(2 * t)", 0.25 * std::f64::consts::PI) - 1.).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn invalid_expressions() {
+        for s in ["", "1 +", "(t", "sin t", "foo(t)", "1.2.3", "t t", "2 * )"] {
+            assert!(s.parse::<Expression>().is_err(), "{s:?} should be invalid");
+        }
+    }
+}
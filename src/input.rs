@@ -1,29 +1,285 @@
+use std::cell::{Cell, RefCell};
 use std::fs::File;
 use std::io::{BufReader, prelude::*};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
 use std::str::FromStr;
 
+use regex::Regex;
+
+use crate::opt::{Aggregate, NonFinite, OnError, Unmatched};
+use crate::time::{self, Axis, Timestamp, TimestampParseError};
+use crate::units::{self, Unit};
+
 #[derive(Debug, PartialEq, PartialOrd)]
 pub struct Line<T>(T);
 
 impl<T> Line<T> {
-    fn parse_value(s: &str) -> Result<Option<f64>, LineParseError> {
-        if s.is_empty() || s == "null" {
-            Ok(None)
+    /// Parse a value from `s`, which is part of `line`
+    fn parse_value(line: &str, s: &str, format: &Format) -> Result<Option<f64>, LineParseError> {
+        if s.is_empty() || format.null_values.iter().any(|null| null == s) {
+            return Ok(None);
+        }
+
+        let parsed = format.unit.as_ref().and_then(|_| units::parse(s));
+        let (value, unit) = match parsed {
+            Some(parsed) => parsed,
+            None => {
+                let value = s.parse().map_err(|err| LineParseError::ParseFloat {
+                    inner: err,
+                    value: s.to_string(),
+                    column: column(line, s),
+                })?;
+                (value, None)
+            }
+        };
+
+        if let (Some(seen), Some(unit)) = (&format.unit, unit) {
+            match seen.get() {
+                Some(expected) if expected != unit => {
+                    return Err(LineParseError::MixedUnits {
+                        value: s.to_string(),
+                        column: column(line, s),
+                        expected,
+                    });
+                }
+                _ => seen.set(Some(unit)),
+            }
+        }
+
+        // Infinite values that are clipped are kept until the range of the graph is known
+        let is_drawn =
+            value.is_finite() || (value.is_infinite() && format.non_finite == NonFinite::Clip);
+        Ok(is_drawn.then_some(value))
+    }
+
+    /// The parts of `s` with each value, from the captures of the pattern if there is one, or
+    /// from `split` if there isn't
+    fn fields<'a>(
+        s: &'a str,
+        format: &Format,
+        split: impl FnOnce(&'a str) -> Vec<&'a str>,
+    ) -> Vec<&'a str> {
+        match &format.pattern {
+            Some(pattern) => pattern.values(s),
+            None => split(s),
+        }
+    }
+}
+
+/// How values are written in the input
+#[derive(Debug, Clone)]
+pub struct Format {
+    /// Values that are missing, besides empty ones
+    pub null_values: Vec<String>,
+    /// What's done with values that are NaN or infinite
+    pub non_finite: NonFinite,
+    /// What comment lines start with, or nothing if there aren't any
+    pub comment_prefix: String,
+    /// Where the unit of the values is kept, if they can be written with units
+    pub unit: Option<Rc<Cell<Option<Unit>>>>,
+    /// What picks the values out of each line, instead of splitting it
+    pub pattern: Option<Pattern>,
+    /// What's done with lines the pattern doesn't match
+    pub unmatched: Unmatched,
+}
+
+impl Format {
+    /// Whether a line is a comment, and should be skipped
+    #[must_use]
+    pub fn is_comment(&self, line: &str) -> bool {
+        !self.comment_prefix.is_empty() && line.trim_start().starts_with(&self.comment_prefix)
+    }
+
+    /// Whether there's a pattern that a line doesn't match
+    #[must_use]
+    pub fn is_unmatched(&self, line: &str) -> bool {
+        self.pattern
+            .as_ref()
+            .is_some_and(|pattern| !pattern.is_match(line))
+    }
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self {
+            null_values: vec![String::from("null")],
+            non_finite: NonFinite::default(),
+            comment_prefix: String::from("#"),
+            unit: None,
+            pattern: None,
+            unmatched: Unmatched::default(),
+        }
+    }
+}
+
+/// Parsing a line of input, given how its values are written
+pub trait ParseLine: Sized {
+    fn parse_line(s: &str, format: &Format) -> Result<Self, LineParseError>;
+}
+
+impl<T> FromStr for Line<T>
+where
+    Self: ParseLine,
+{
+    type Err = LineParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_line(s, &Format::default())
+    }
+}
+
+/// A regular expression that picks the values out of each line
+#[derive(Debug, Clone)]
+pub struct Pattern {
+    regex: Regex,
+    /// The capture groups with values, in order
+    groups: Vec<usize>,
+}
+
+impl Pattern {
+    /// Use the named capture groups of `regex` if it has any, or all of them if not
+    ///
+    /// Without any capture groups, the whole match is the only value.
+    #[must_use]
+    pub fn new(regex: Regex) -> Self {
+        let named: Vec<usize> = regex
+            .capture_names()
+            .enumerate()
+            .filter_map(|(group, name)| name.map(|_| group))
+            .collect();
+
+        let groups = if !named.is_empty() {
+            named
+        } else if regex.captures_len() > 1 {
+            (1..regex.captures_len()).collect()
         } else {
-            Some(s.parse().map_err(|err| LineParseError::ParseFloat {
-                inner: err,
-                value: s.to_string(),
-            }))
-            .transpose()
+            vec![0]
+        };
+
+        Self { regex, groups }
+    }
+
+    /// How many values each line has
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    /// The names of the capture groups with values, if they're named
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.regex.capture_names().flatten()
+    }
+
+    /// Whether a line has values to pick out
+    #[must_use]
+    pub fn is_match(&self, line: &str) -> bool {
+        self.regex.is_match(line)
+    }
+
+    /// The text of each value in `line`, which is empty for groups that didn't match
+    fn values<'a>(&self, line: &'a str) -> Vec<&'a str> {
+        let Some(captures) = self.regex.captures(line) else {
+            return vec![];
+        };
+
+        self.groups
+            .iter()
+            .map(|group| {
+                captures
+                    .get(*group)
+                    .map_or(&line[line.len()..], |value| value.as_str())
+            })
+            .collect()
+    }
+}
+
+/// Where `part` (a slice of `line`) starts in the line, counting characters from 1
+fn column(line: &str, part: &str) -> usize {
+    let offset = part.as_ptr().addr() - line.as_ptr().addr();
+    line[..offset].chars().count() + 1
+}
+
+/// A line that's drawn as a gap, in place of one that couldn't be parsed
+pub trait Gap: Sized {
+    /// The gap, or nothing if there's no way to draw one
+    fn gap() -> Option<Self>;
+}
+
+impl Gap for Line<Option<f64>> {
+    fn gap() -> Option<Self> {
+        Some(Self(None))
+    }
+}
+
+impl<const N: usize> Gap for Line<[Option<f64>; N]> {
+    fn gap() -> Option<Self> {
+        Some(Self([None; N]))
+    }
+}
+
+impl Gap for Line<Vec<Option<f64>>> {
+    fn gap() -> Option<Self> {
+        Some(Self(vec![]))
+    }
+}
+
+/// Without a timestamp, there's nowhere to put the gap
+impl Gap for Line<(Timestamp, Option<f64>)> {
+    fn gap() -> Option<Self> {
+        None
+    }
+}
+
+/// A line made from one value of each series, when they're read from separate files
+pub trait FromValues: Sized {
+    fn from_values(values: Vec<Option<f64>>) -> Result<Self, LineParseError>;
+}
+
+impl FromValues for Line<Option<f64>> {
+    fn from_values(values: Vec<Option<f64>>) -> Result<Self, LineParseError> {
+        match values[..] {
+            [value] => Ok(Self(value)),
+            _ => Err(LineParseError::WrongNumValues {
+                expected: 1,
+                actual: values.len(),
+            }),
         }
     }
 }
 
+impl<const N: usize> FromValues for Line<[Option<f64>; N]> {
+    fn from_values(values: Vec<Option<f64>>) -> Result<Self, LineParseError> {
+        let actual = values.len();
+        values
+            .try_into()
+            .map(Self)
+            .map_err(|_| LineParseError::WrongNumValues {
+                expected: N,
+                actual,
+            })
+    }
+}
+
+impl FromValues for Line<Vec<Option<f64>>> {
+    fn from_values(values: Vec<Option<f64>>) -> Result<Self, LineParseError> {
+        Ok(Self(values))
+    }
+}
+
+impl FromValues for Line<(Timestamp, Option<f64>)> {
+    fn from_values(_: Vec<Option<f64>>) -> Result<Self, LineParseError> {
+        unreachable!("Time series can't be read from more than one file")
+    }
+}
+
 pub trait LineSinglable<'a> {
     type Iter: Iterator<Item = &'a Option<f64>>;
 
     fn as_single_iter(&'a self) -> Self::Iter;
+
+    /// Replace each value with the result of `f`, given the value's series and the value
+    fn map_values(&mut self, f: impl FnMut(usize, f64) -> f64);
 }
 
 impl<'a> LineSinglable<'a> for Line<Option<f64>> {
@@ -32,6 +288,10 @@ impl<'a> LineSinglable<'a> for Line<Option<f64>> {
     fn as_single_iter(&'a self) -> Self::Iter {
         std::iter::once(&self.0)
     }
+
+    fn map_values(&mut self, mut f: impl FnMut(usize, f64) -> f64) {
+        self.0 = self.0.map(|value| f(0, value));
+    }
 }
 
 impl<'a, const N: usize> LineSinglable<'a> for Line<[Option<f64>; N]> {
@@ -40,6 +300,12 @@ impl<'a, const N: usize> LineSinglable<'a> for Line<[Option<f64>; N]> {
     fn as_single_iter(&'a self) -> Self::Iter {
         self.0.iter()
     }
+
+    fn map_values(&mut self, mut f: impl FnMut(usize, f64) -> f64) {
+        for (index, value) in self.0.iter_mut().enumerate() {
+            *value = value.map(|value| f(index, value));
+        }
+    }
 }
 
 impl<'a> LineSinglable<'a> for Line<Vec<Option<f64>>> {
@@ -48,19 +314,34 @@ impl<'a> LineSinglable<'a> for Line<Vec<Option<f64>>> {
     fn as_single_iter(&'a self) -> Self::Iter {
         self.0.iter()
     }
+
+    fn map_values(&mut self, mut f: impl FnMut(usize, f64) -> f64) {
+        for (index, value) in self.0.iter_mut().enumerate() {
+            *value = value.map(|value| f(index, value));
+        }
+    }
 }
 
 impl Line<Option<f64>> {
+    #[must_use]
+    pub fn new(value: Option<f64>) -> Self {
+        Self(value)
+    }
+
     pub fn into_inner(self) -> Option<f64> {
         self.0
     }
 }
 
-impl FromStr for Line<Option<f64>> {
-    type Err = LineParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Self(Self::parse_value(s)?))
+impl ParseLine for Line<Option<f64>> {
+    fn parse_line(s: &str, format: &Format) -> Result<Self, LineParseError> {
+        match Self::fields(s, format, |s| vec![s])[..] {
+            [value] => Ok(Self(Self::parse_value(s, value, format)?)),
+            ref values => Err(LineParseError::WrongNumValues {
+                expected: 1,
+                actual: values.len(),
+            }),
+        }
     }
 }
 
@@ -69,35 +350,82 @@ pub enum LineParseError {
     ParseFloat {
         inner: std::num::ParseFloatError,
         value: String,
+        column: usize,
     },
     WrongNumValues {
         expected: usize,
         actual: usize,
     },
+    ParseTimestamp(TimestampParseError),
+    Read {
+        line: usize,
+        kind: std::io::ErrorKind,
+        message: String,
+    },
+    InvalidUtf8 {
+        line: usize,
+    },
+    MixedUnits {
+        value: String,
+        column: usize,
+        expected: Unit,
+    },
+    OnLine {
+        line: usize,
+        inner: Box<LineParseError>,
+    },
+    InFile {
+        path: String,
+        inner: Box<LineParseError>,
+    },
 }
 
 impl std::fmt::Display for LineParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            LineParseError::ParseFloat { inner, value } => {
-                write!(f, "Failed to parse {value:?}: {inner}")
+            LineParseError::ParseFloat {
+                inner,
+                value,
+                column,
+            } => {
+                write!(f, "Failed to parse {value:?} at column {column}: {inner}")
             }
             LineParseError::WrongNumValues { expected, actual } => {
                 write!(f, "Expected line with {expected} values, found {actual}")
             }
+            LineParseError::ParseTimestamp(inner) => inner.fmt(f),
+            LineParseError::Read { line, message, .. } => {
+                write!(f, "Failed to read line {line}: {message}")
+            }
+            LineParseError::InvalidUtf8 { line } => {
+                write!(
+                    f,
+                    "Line {line} isn't valid UTF-8 (use --lossy to replace invalid bytes)"
+                )
+            }
+            LineParseError::MixedUnits {
+                value,
+                column,
+                expected,
+            } => {
+                write!(
+                    f,
+                    "Expected a value in {expected}, found {value:?} at column {column}"
+                )
+            }
+            LineParseError::OnLine { line, inner } => write!(f, "Line {line}: {inner}"),
+            LineParseError::InFile { path, inner } => write!(f, "{path}: {inner}"),
         }
     }
 }
 
 impl std::error::Error for LineParseError {}
 
-impl<const N: usize> FromStr for Line<[Option<f64>; N]> {
-    type Err = LineParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let line: Vec<_> = s
-            .splitn(N, |c: char| c.is_ascii_whitespace())
-            .map(Self::parse_value)
+impl<const N: usize> ParseLine for Line<[Option<f64>; N]> {
+    fn parse_line(s: &str, format: &Format) -> Result<Self, LineParseError> {
+        let line: Vec<_> = Self::fields(s, format, split_values)
+            .into_iter()
+            .map(|value| Self::parse_value(s, value, format))
             .collect::<Result<_, _>>()?;
         Ok(Self(<[_; N]>::try_from(line).map_err(|line_values| {
             LineParseError::WrongNumValues {
@@ -108,18 +436,50 @@ impl<const N: usize> FromStr for Line<[Option<f64>; N]> {
     }
 }
 
-impl FromStr for Line<Vec<Option<f64>>> {
-    type Err = LineParseError;
+/// Split a line into values separated by whitespace, or by commas (like CSV) if there are any
+fn split_values(s: &str) -> Vec<&str> {
+    if s.contains(',') {
+        s.split(',').map(str::trim).collect()
+    } else {
+        s.split(|c: char| c.is_ascii_whitespace()).collect()
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let line: Vec<_> = s
-            .split(|c: char| c.is_ascii_whitespace())
-            .map(Self::parse_value)
+impl ParseLine for Line<Vec<Option<f64>>> {
+    fn parse_line(s: &str, format: &Format) -> Result<Self, LineParseError> {
+        let line: Vec<_> = Self::fields(s, format, split_values)
+            .into_iter()
+            .map(|value| Self::parse_value(s, value, format))
             .collect::<Result<_, _>>()?;
         Ok(Self(line))
     }
 }
 
+/// A timestamp followed by a value, separated by whitespace
+impl ParseLine for Line<(Timestamp, Option<f64>)> {
+    fn parse_line(s: &str, format: &Format) -> Result<Self, LineParseError> {
+        let fields = Self::fields(s, format, |s| {
+            let (timestamp, value) = s
+                .trim()
+                .split_once(|c: char| c.is_ascii_whitespace())
+                .unwrap_or((s.trim(), ""));
+            vec![timestamp, value]
+        });
+        let [timestamp, value] = fields[..] else {
+            return Err(LineParseError::WrongNumValues {
+                expected: 2,
+                actual: fields.len(),
+            });
+        };
+
+        let timestamp = timestamp.parse().map_err(LineParseError::ParseTimestamp)?;
+        Ok(Self((
+            timestamp,
+            Self::parse_value(s, value.trim(), format)?,
+        )))
+    }
+}
+
 impl<T> IntoIterator for Line<T>
 where
     T: IntoIterator<Item = Option<f64>>,
@@ -153,38 +513,399 @@ where
     }
 }
 
+/// How lines are read from the input
+#[derive(Debug, Clone)]
+pub struct ReadOptions {
+    /// The number of the first line read, counting from 1
+    pub line_number: usize,
+    /// Replace invalid UTF-8 instead of stopping with an error
+    pub lossy: bool,
+    /// What's done with lines that can't be parsed
+    pub on_error: OnError,
+    /// Where the numbers of lines that couldn't be parsed are kept, if they don't fail
+    pub skipped: Rc<RefCell<Vec<usize>>>,
+    /// How values are written
+    pub format: Format,
+}
+
+impl Default for ReadOptions {
+    fn default() -> Self {
+        Self {
+            line_number: 1,
+            lossy: false,
+            on_error: OnError::default(),
+            skipped: Rc::default(),
+            format: Format::default(),
+        }
+    }
+}
+
+/// Read each line of the input, stopping after the first one that can't be read
+///
+/// Line endings (`\n` or `\r\n`) are removed, like [`BufRead::lines`].
+pub fn lines(
+    reader: impl BufRead,
+    options: ReadOptions,
+) -> impl Iterator<Item = Result<String, LineParseError>> {
+    let mut failed = false;
+    reader
+        .split(b'\n')
+        .zip(options.line_number..)
+        .map_while(move |(bytes, line)| {
+            if failed {
+                return None;
+            }
+
+            let result = bytes
+                .map_err(|err| LineParseError::Read {
+                    line,
+                    kind: err.kind(),
+                    message: err.to_string(),
+                })
+                .and_then(|bytes| decode(bytes, line, options.lossy));
+            failed = result.is_err();
+            Some(result)
+        })
+}
+
+/// Turn the bytes of a line into a string, without its line ending
+fn decode(mut bytes: Vec<u8>, line: usize, lossy: bool) -> Result<String, LineParseError> {
+    if bytes.last() == Some(&b'\n') {
+        bytes.pop();
+    }
+
+    if bytes.last() == Some(&b'\r') {
+        bytes.pop();
+    }
+
+    if lossy {
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    } else {
+        String::from_utf8(bytes).map_err(|_| LineParseError::InvalidUtf8 { line })
+    }
+}
+
+/// Open the file at `path` for reading, or standard input if there isn't one (or it's `-`)
+pub fn open(path: Option<&Path>) -> std::io::Result<Box<dyn BufRead>> {
+    match path {
+        None => Ok(Box::new(std::io::stdin().lock())),
+        Some(path) if path.as_os_str() == "-" => Ok(Box::new(std::io::stdin().lock())),
+        Some(path) => {
+            let file = File::open(path)?;
+            Ok(Box::new(BufReader::new(file)))
+        }
+    }
+}
+
+/// How many lines each file is read ahead of the graph, when reading more than one
+const LINES_AHEAD: usize = 1024;
+
 impl<T: 'static> Lines<T>
 where
     Line<T>: FromStr,
 {
-    pub fn try_from_path(first_line: Option<String>, path: Option<&Path>) -> anyhow::Result<Self> {
-        match path {
-            None => Ok(Self::from_buf_reader(first_line, std::io::stdin().lock())),
-            Some(path) if path.as_os_str() == "-" => {
-                Ok(Self::from_buf_reader(first_line, std::io::stdin().lock()))
-            }
-            Some(path) => {
-                let file = File::open(path)?;
-                let reader = BufReader::new(file);
-                Ok(Self::from_buf_reader(first_line, reader))
-            }
+    /// Read each file as its own series, with one value from each of them on every line
+    ///
+    /// Each file is read on its own thread, and the lines at the same place in every file are
+    /// put together here. Files that run out of lines before the rest are padded with gaps. `-`
+    /// is standard input. A line that's skipped in any file, because it's a comment, doesn't
+    /// match the pattern, or can't be parsed, is skipped in every file, so the series stay lined
+    /// up.
+    pub fn from_files(paths: &[PathBuf], options: &ReadOptions) -> std::io::Result<Self>
+    where
+        Line<T>: FromStr<Err = LineParseError> + FromValues,
+    {
+        let ReadOptions {
+            line_number,
+            lossy,
+            on_error,
+            skipped,
+            format,
+        } = options.clone();
+
+        let files = paths
+            .iter()
+            .map(|path| {
+                // Files are opened here, so not finding one is reported before anything is read
+                let file = (path.as_os_str() != "-")
+                    .then(|| File::open(path))
+                    .transpose()?;
+                let (sender, receiver) = std::sync::mpsc::sync_channel(LINES_AHEAD);
+                std::thread::spawn(move || {
+                    let reader: Box<dyn BufRead> = match file {
+                        Some(file) => Box::new(BufReader::new(file)),
+                        None => Box::new(std::io::stdin().lock()),
+                    };
+                    let options = ReadOptions {
+                        line_number,
+                        lossy,
+                        ..ReadOptions::default()
+                    };
+
+                    // Stop reading once the lines aren't wanted anymore
+                    for line in lines(reader, options) {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
+                });
+                Ok((path.display().to_string(), receiver))
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+
+        let mut line_numbers = line_number..;
+        Ok(Self {
+            iter: Box::new(std::iter::from_fn(move || {
+                for line_number in line_numbers.by_ref() {
+                    let mut lines = Vec::with_capacity(files.len());
+                    for (path, receiver) in &files {
+                        match receiver.recv().ok().transpose() {
+                            Ok(line) => lines.push((path.as_str(), line)),
+                            // Lines that can't be read always fail, since there's nothing to skip
+                            Err(err) => {
+                                return Some(Err(LineParseError::InFile {
+                                    path: path.clone(),
+                                    inner: Box::new(err),
+                                }));
+                            }
+                        }
+                    }
+
+                    if lines.iter().all(|(_, line)| line.is_none()) {
+                        return None;
+                    }
+
+                    let is_skipped = |line: &str| {
+                        format.is_comment(line)
+                            || (format.is_unmatched(line)
+                                && matches!(format.unmatched, Unmatched::Skip))
+                    };
+                    if lines
+                        .iter()
+                        .any(|(_, line)| line.as_deref().is_some_and(is_skipped))
+                    {
+                        continue;
+                    }
+
+                    let mut is_unparsed = false;
+                    let mut values = Vec::with_capacity(lines.len());
+                    for (path, line) in lines {
+                        let Some(line) = line.filter(|line| !format.is_unmatched(line)) else {
+                            values.push(None);
+                            continue;
+                        };
+
+                        match Line::<Option<f64>>::parse_line(&line, &format) {
+                            Ok(line) => values.push(line.into_inner()),
+                            Err(err) if matches!(on_error, OnError::Fail) => {
+                                return Some(Err(LineParseError::InFile {
+                                    path: path.to_string(),
+                                    inner: Box::new(LineParseError::OnLine {
+                                        line: line_number,
+                                        inner: Box::new(err),
+                                    }),
+                                }));
+                            }
+                            Err(_) => {
+                                is_unparsed = true;
+                                values.push(None);
+                            }
+                        }
+                    }
+
+                    if is_unparsed {
+                        skipped.borrow_mut().push(line_number);
+                        if matches!(on_error, OnError::Skip) {
+                            continue;
+                        }
+                    }
+
+                    return Some(Line::from_values(values));
+                }
+
+                None
+            })),
+        })
+    }
+
+    /// Change each line that was parsed with `f`
+    #[must_use]
+    pub fn map_lines(self, mut f: impl FnMut(Line<T>) -> Line<T> + 'static) -> Self {
+        Self {
+            iter: Box::new(self.iter.map(move |line| line.map(&mut f))),
         }
     }
 
-    pub fn from_buf_reader<R: BufRead + 'static>(first_line: Option<String>, reader: R) -> Self {
+    /// Stop after `count` lines
+    #[must_use]
+    pub fn take_lines(self, count: usize) -> Self {
+        Self {
+            iter: Box::new(self.iter.take(count)),
+        }
+    }
+
+    /// Like [`Lines::from_buf_reader`], but the first line is returned separately as a header
+    pub fn from_buf_reader_with_header<R: BufRead + 'static>(
+        first_line: Option<String>,
+        mut reader: R,
+        options: ReadOptions,
+    ) -> Result<(Option<String>, Self), LineParseError>
+    where
+        Line<T>: FromStr<Err = LineParseError> + ParseLine + Gap,
+    {
+        let header = match first_line {
+            Some(line) => Some(line),
+            None => {
+                let mut bytes = vec![];
+                let read =
+                    reader
+                        .read_until(b'\n', &mut bytes)
+                        .map_err(|err| LineParseError::Read {
+                            line: options.line_number,
+                            kind: err.kind(),
+                            message: err.to_string(),
+                        })?;
+
+                if read == 0 {
+                    None
+                } else {
+                    Some(decode(bytes, options.line_number, options.lossy)?)
+                }
+            }
+        };
+
+        let options = ReadOptions {
+            line_number: options.line_number + 1,
+            ..options
+        };
+        Ok((header, Self::from_buf_reader(None, reader, options)))
+    }
+
+    /// Parse each line of the input, with `first_line` coming before the rest if it's already
+    /// been read
+    pub fn from_buf_reader<R: BufRead + 'static>(
+        first_line: Option<String>,
+        reader: R,
+        options: ReadOptions,
+    ) -> Self
+    where
+        Line<T>: FromStr<Err = LineParseError> + ParseLine + Gap,
+    {
+        let line_number = options.line_number;
+        let on_error = options.on_error;
+        let skipped = Rc::clone(&options.skipped);
+        let format = options.format.clone();
+        let options = ReadOptions {
+            line_number: line_number + usize::from(first_line.is_some()),
+            ..options
+        };
+
         Self {
             iter: Box::new(
                 first_line
                     .into_iter()
                     .map(Ok)
-                    .chain(reader.lines())
-                    .map_while(Result::ok)
-                    .map(|x| x.parse()),
+                    .chain(lines(reader, options))
+                    .zip(line_number..)
+                    .filter_map(move |(line, line_number)| {
+                        // Lines that can't be read always fail, since there's nothing to skip
+                        let line = match line {
+                            Ok(line) if format.is_comment(&line) => return None,
+                            Ok(line) if format.is_unmatched(&line) => {
+                                return match format.unmatched {
+                                    Unmatched::Skip => None,
+                                    Unmatched::Gap => Line::gap().map(Ok),
+                                };
+                            }
+                            line => line,
+                        };
+
+                        let err = match line.map(|line| Line::parse_line(&line, &format)) {
+                            Ok(Ok(line)) => return Some(Ok(line)),
+                            Ok(Err(err)) => err,
+                            Err(err) => return Some(Err(err)),
+                        };
+
+                        match on_error {
+                            OnError::Fail => Some(Err(LineParseError::OnLine {
+                                line: line_number,
+                                inner: Box::new(err),
+                            })),
+                            OnError::Skip => {
+                                skipped.borrow_mut().push(line_number);
+                                None
+                            }
+                            OnError::Null => {
+                                skipped.borrow_mut().push(line_number);
+                                Line::gap().map(Ok)
+                            }
+                        }
+                    }),
             ),
         }
     }
 }
 
+impl Lines<Vec<Option<f64>>> {
+    /// Split each line into one line per value, with an empty line between each group
+    ///
+    /// Each line must have exactly `per` values.
+    pub fn into_grouped(self, per: usize) -> Lines<Option<f64>> {
+        let mut is_first = true;
+        Lines {
+            iter: Box::new(self.flat_map(move |line| {
+                let separator = if is_first { None } else { Some(Ok(Line(None))) };
+                is_first = false;
+
+                let values = line.and_then(|Line(values)| {
+                    if values.is_empty() {
+                        // A gap in place of a line that couldn't be parsed
+                        Ok(vec![None; per])
+                    } else if values.len() == per {
+                        Ok(values)
+                    } else {
+                        Err(LineParseError::WrongNumValues {
+                            expected: per,
+                            actual: values.len(),
+                        })
+                    }
+                });
+
+                let group: Vec<LineResult<Option<f64>>> = match values {
+                    Ok(values) => values.into_iter().map(|value| Ok(Line(value))).collect(),
+                    Err(err) => vec![Err(err)],
+                };
+
+                separator.into_iter().chain(group)
+            })),
+        }
+    }
+}
+
+impl Lines<(Timestamp, Option<f64>)> {
+    /// Read every line, and combine the values into `count` evenly spaced buckets of time
+    ///
+    /// The span of time covered is returned alongside the values, if there were any lines.
+    pub fn into_time_series(
+        self,
+        count: usize,
+        aggregate: Aggregate,
+    ) -> Result<(Lines<Option<f64>>, Option<Axis>), LineParseError> {
+        let samples = self
+            .map(|line| line.map(|Line(sample)| sample))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let values = time::bucket(&samples, count, aggregate);
+
+        Ok((
+            Lines {
+                iter: Box::new(values.into_iter().map(|value| Ok(Line(value)))),
+            },
+            Axis::from_samples(&samples),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -199,7 +920,11 @@ mod tests {
             Ok(Line([Some(4.), Some(5.), Some(6.)])),
         ];
 
-        let iter = Lines::<[Option<f64>; 3]>::from_buf_reader(None, Cursor::new(input));
+        let iter = Lines::<[Option<f64>; 3]>::from_buf_reader(
+            None,
+            Cursor::new(input),
+            ReadOptions::default(),
+        );
         let actual: Vec<_> = iter.collect();
         assert_eq!(expected, actual);
     }
@@ -218,8 +943,215 @@ mod tests {
             Ok(Line(Some(6.))),
         ];
 
-        let iter = Lines::<Option<f64>>::from_buf_reader(None, Cursor::new(input));
+        let iter =
+            Lines::<Option<f64>>::from_buf_reader(None, Cursor::new(input), ReadOptions::default());
         let actual: Vec<_> = iter.collect();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn check_lines_iter_comma_separated() {
+        use std::io::Cursor;
+
+        let input = "1, 2,3\n4,,null";
+        let expected: Vec<LineResult<Vec<Option<f64>>>> = vec![
+            Ok(Line(vec![Some(1.), Some(2.), Some(3.)])),
+            Ok(Line(vec![Some(4.), None, None])),
+        ];
+
+        let iter = Lines::<Vec<Option<f64>>>::from_buf_reader(
+            None,
+            Cursor::new(input),
+            ReadOptions::default(),
+        );
+        let actual: Vec<_> = iter.collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn check_lines_iter_null_values_and_comments() {
+        use std::io::Cursor;
+
+        let input = "# values\n1 N/A\nNaN -\n  ; skipped\ninf -inf";
+        let expected: Vec<LineResult<[Option<f64>; 2]>> = vec![
+            Ok(Line([Some(1.), None])),
+            Ok(Line([None, None])),
+            Ok(Line([None, None])),
+        ];
+
+        let format = Format {
+            null_values: vec![String::from("N/A"), String::from("-")],
+            comment_prefix: String::from(";"),
+            ..Format::default()
+        };
+        let options = ReadOptions {
+            format,
+            ..ReadOptions::default()
+        };
+
+        // Only the first line is a comment with the default prefix
+        let mut iter = Lines::<[Option<f64>; 2]>::from_buf_reader(
+            None,
+            Cursor::new(input),
+            ReadOptions::default(),
+        );
+        assert!(iter.nth(1).unwrap().is_err());
+
+        let iter = Lines::<[Option<f64>; 2]>::from_buf_reader(
+            None,
+            Cursor::new(input.trim_start_matches("# values\n")),
+            options,
+        );
+        let actual: Vec<_> = iter.collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn check_lines_iter_pattern() {
+        use std::io::Cursor;
+
+        let input = "took 12ms queue=3\nstarting up\ntook 8ms queue=";
+        let read = |unmatched| {
+            let format = Format {
+                pattern: Some(Pattern::new(
+                    Regex::new(r"took (\d+)ms queue=(\d*)").unwrap(),
+                )),
+                unmatched,
+                ..Format::default()
+            };
+            let options = ReadOptions {
+                format,
+                ..ReadOptions::default()
+            };
+            Lines::<[Option<f64>; 2]>::from_buf_reader(None, Cursor::new(input), options)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec![Ok(Line([Some(12.), Some(3.)])), Ok(Line([Some(8.), None]))],
+            read(Unmatched::Skip)
+        );
+        assert_eq!(
+            vec![
+                Ok(Line([Some(12.), Some(3.)])),
+                Ok(Line([None, None])),
+                Ok(Line([Some(8.), None])),
+            ],
+            read(Unmatched::Gap)
+        );
+    }
+
+    #[test]
+    fn check_pattern_groups() {
+        let pattern = |regex| Pattern::new(Regex::new(regex).unwrap());
+
+        assert_eq!(1, pattern(r"\d+").len());
+        assert_eq!(2, pattern(r"(\d+) (\d+)").len());
+
+        // Only the named groups are values
+        let named = pattern(r"(GET|POST) (?<took>\d+)ms (?<queue>\d+)");
+        assert_eq!(2, named.len());
+        assert_eq!(vec!["took", "queue"], named.names().collect::<Vec<_>>());
+        assert_eq!(vec!["12", "3"], named.values("GET 12ms 3"));
+    }
+
+    #[test]
+    fn check_clipped_values() {
+        let format = Format {
+            non_finite: NonFinite::Clip,
+            ..Format::default()
+        };
+
+        assert_eq!(
+            Line([Some(f64::INFINITY), Some(f64::NEG_INFINITY), None]),
+            Line::<[Option<f64>; 3]>::parse_line("inf -inf NaN", &format).unwrap()
+        );
+    }
+
+    #[test]
+    fn check_lines_iter_grouped() {
+        use std::io::Cursor;
+
+        let input = "1 2\n3 null";
+        let expected: Vec<LineResult<Option<f64>>> = vec![
+            Ok(Line(Some(1.))),
+            Ok(Line(Some(2.))),
+            Ok(Line(None)),
+            Ok(Line(Some(3.))),
+            Ok(Line(None)),
+        ];
+
+        let iter = Lines::<Vec<Option<f64>>>::from_buf_reader(
+            None,
+            Cursor::new(input),
+            ReadOptions::default(),
+        );
+        let actual: Vec<_> = iter.into_grouped(2).collect();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn check_lines_iter_time_series() {
+        use std::io::Cursor;
+
+        let input = "1970-01-01T00:00:00Z 1\n3 null\n4 3\n10 5";
+        let expected: Vec<LineResult<Option<f64>>> =
+            vec![Ok(Line(Some(1.))), Ok(Line(Some(3.))), Ok(Line(Some(5.)))];
+
+        let iter = Lines::<(Timestamp, Option<f64>)>::from_buf_reader(
+            None,
+            Cursor::new(input),
+            ReadOptions::default(),
+        );
+        let (lines, axis) = iter.into_time_series(3, Aggregate::Avg).unwrap();
+        assert_eq!(expected, lines.collect::<Vec<_>>());
+        assert!(axis.is_some());
+    }
+
+    #[test]
+    fn check_lines_invalid_utf8() {
+        use std::io::Cursor;
+
+        let input = b"1\r\n\xff2\n3".to_vec();
+        let expected = vec![
+            Ok(String::from("1")),
+            Err(LineParseError::InvalidUtf8 { line: 2 }),
+        ];
+        let actual: Vec<_> = lines(Cursor::new(input.clone()), ReadOptions::default()).collect();
+        assert_eq!(expected, actual);
+
+        let options = ReadOptions {
+            lossy: true,
+            ..ReadOptions::default()
+        };
+        let actual: Vec<_> = lines(Cursor::new(input), options)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(vec!["1", "\u{fffd}2", "3"], actual);
+    }
+
+    #[test]
+    fn check_lines_read_error() {
+        struct Broken;
+
+        impl Read for Broken {
+            fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("broken"))
+            }
+        }
+
+        let options = ReadOptions {
+            line_number: 5,
+            ..ReadOptions::default()
+        };
+        let actual: Vec<_> = lines(BufReader::new(Broken), options).collect();
+        assert_eq!(
+            vec![Err(LineParseError::Read {
+                line: 5,
+                kind: std::io::ErrorKind::Other,
+                message: String::from("broken"),
+            })],
+            actual
+        );
+    }
 }
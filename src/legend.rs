@@ -0,0 +1,199 @@
+//! Titles and legends printed around a graph.
+//!
+//! # Example
+//!
+//! ```console
+//! $ paste <(seq 1 8) <(seq 8 -1 1) | braille -c 2 -p2 --stack --title Totals --legend up,down
+//! Totals
+//! ⠭⠭⣭⣽
+//! ⣽⣿⣿⣿
+//! ⣿⣿ up: min 1, max 8, last 8
+//! ⣒⣒ down: min 1, max 8, last 1
+//! ```
+
+use crate::graph::glyphs::{GlyphSet, WIDTH};
+use crate::graph::{STACK_SHADES, STACK_STRIDES};
+use crate::opt::Orientation;
+use crate::units::{self, Unit};
+
+/// The smallest, largest, and last value seen in a series
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SeriesStats {
+    pub min: f64,
+    pub max: f64,
+    pub last: f64,
+}
+
+impl SeriesStats {
+    #[must_use]
+    pub fn new(value: f64) -> Self {
+        Self {
+            min: value,
+            max: value,
+            last: value,
+        }
+    }
+
+    pub fn update(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.last = value;
+    }
+}
+
+/// Cut a line down to fit within `width` characters
+#[must_use]
+pub fn truncate(line: &str, width: usize) -> String {
+    line.chars().take(width).collect()
+}
+
+/// One line per series, with its name and statistics
+///
+/// Series without a name are numbered instead, and names without a series are skipped. When
+/// `samples` are given, each line starts with the matching sample. Values are written in `unit`,
+/// if there is one.
+#[must_use]
+pub fn footer(
+    names: &[String],
+    stats: &[Option<SeriesStats>],
+    samples: Option<&[String]>,
+    unit: Option<Unit>,
+    width: usize,
+) -> Vec<String> {
+    stats
+        .iter()
+        .enumerate()
+        .map(|(index, stats)| {
+            let mut line = String::new();
+
+            if let Some(sample) = samples.and_then(|samples| samples.get(index)) {
+                line.push_str(sample);
+                line.push(' ');
+            }
+
+            match names.get(index) {
+                Some(name) => line.push_str(name),
+                None => line.push_str(&(index + 1).to_string()),
+            }
+
+            if let Some(SeriesStats { min, max, last }) = stats {
+                let [min, max, last] = [min, max, last].map(|value| units::label(*value, unit));
+                line.push_str(&format!(": min {min}, max {max}, last {last}"));
+            }
+
+            truncate(&line, width)
+        })
+        .collect()
+}
+
+/// Two characters of the glyph set `G` showing how each stacked series is filled
+///
+/// Each series is filled with every nth dot along the graph's values (see [`STACK_STRIDES`]).
+#[must_use]
+pub fn stack_samples<G: GlyphSet<ROWS>, const ROWS: usize>(
+    series: usize,
+    orientation: Orientation,
+) -> Vec<String> {
+    (0..series)
+        .map(|index| {
+            let stride = STACK_STRIDES[index % STACK_STRIDES.len()];
+            let is_filled = |dot: usize| dot.is_multiple_of(stride);
+
+            match orientation {
+                // Values go left to right, across both characters
+                Orientation::Horizontal => (0..2)
+                    .map(|character| {
+                        let left = is_filled(character * 2);
+                        let right = is_filled(character * 2 + 1);
+                        G::glyph([[left, right]; ROWS])
+                    })
+                    .collect(),
+                // Values go from the bottom to the top of each character
+                Orientation::Vertical => {
+                    let mut dots = [[false; WIDTH]; ROWS];
+                    for (row, pair) in dots.iter_mut().rev().enumerate() {
+                        *pair = [is_filled(row); WIDTH];
+                    }
+
+                    G::glyph(dots).to_string().repeat(2)
+                }
+            }
+        })
+        .collect()
+}
+
+/// Two blocks showing the shade each stacked series of a block graph is filled with
+#[must_use]
+pub fn stack_shade_samples(series: usize) -> Vec<String> {
+    (0..series)
+        .map(|index| STACK_SHADES[index % STACK_SHADES.len()].repeat(2))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{braille::Braille, mini_blocks::Quadrants, sextants::Sextants};
+
+    #[test]
+    fn footer_names_and_stats() {
+        let names = [String::from("up")];
+        let stats = [
+            Some(SeriesStats {
+                min: -1.5,
+                max: 8.,
+                last: 2.,
+            }),
+            None,
+        ];
+
+        assert_eq!(
+            vec![
+                String::from("up: min -1.5, max 8, last 2"),
+                String::from("2"),
+            ],
+            footer(&names, &stats, None, None, 80)
+        );
+    }
+
+    #[test]
+    fn footer_truncated_with_samples() {
+        let names = [String::from("a long name")];
+        let stats = [Some(SeriesStats::new(1.))];
+        let samples = [String::from("⣿⣿")];
+
+        assert_eq!(
+            vec![String::from("⣿⣿ a long")],
+            footer(&names, &stats, Some(&samples), None, 9)
+        );
+    }
+
+    #[test]
+    fn samples_for_stacked_series() {
+        assert_eq!(
+            vec!["⣿⣿", "⡇⡇", "⡇⢸", "⣿⣿"],
+            stack_samples::<Braille, 4>(4, Orientation::Horizontal)
+        );
+        assert_eq!(
+            vec!["⣿⣿", "⣒⣒", "⣉⣉"],
+            stack_samples::<Braille, 4>(3, Orientation::Vertical)
+        );
+    }
+
+    #[test]
+    fn samples_for_stacked_blocks() {
+        assert_eq!(vec!["██", "▒▒", "░░", "██"], stack_shade_samples(4));
+    }
+
+    #[test]
+    fn samples_in_other_glyph_sets() {
+        assert_eq!(
+            vec!["██", "▌▌"],
+            stack_samples::<Quadrants, 2>(2, Orientation::Horizontal)
+        );
+        assert_eq!(
+            vec!["██", "🬰🬰"],
+            stack_samples::<Sextants, 3>(2, Orientation::Vertical)
+        );
+    }
+}
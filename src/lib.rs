@@ -1,12 +1,25 @@
+mod annotate;
+pub mod canvas;
+mod config_file;
 pub mod graph;
 pub mod grid;
 mod input;
+mod legend;
 mod opt;
+mod pager;
+mod quantile;
+mod tiles;
+mod time;
+mod units;
 pub mod util;
+mod watch;
 
-use std::io::LineWriter;
 use std::io::prelude::*;
+use std::io::{IsTerminal, LineWriter};
 
+use anyhow::Context;
+
+pub use canvas::Canvas;
 pub use graph::{BarGraphable, ColumnGraphable, Graphable};
 pub use graph::{
     blocks::{Bars as BlockBars, Columns as BlockColumns},
@@ -15,91 +28,507 @@ pub use graph::{
     octants::{Columns as OctantColumns, Lines as OctantBars},
     sextants::{Columns as SextantColumns, Lines as SextantBars},
 };
+use graph::{braille::Braille, mini_blocks::Quadrants, octants::Octants, sextants::Sextants};
 use input::{
     Line as InputLine, LineResult, LineSinglable as InputLineSinglable, Lines as InputLines,
 };
-use opt::{Config, FirstLine};
+use opt::{CharType, Config, Configurable, FirstLine, Orientation, Rewind, ValueIter};
 pub use opt::{GraphKind, GraphStyle, Opt};
 
 /// Main entry point for the program
 pub fn run<W: Write>(opt: Opt, writer: LineWriter<W>) -> anyhow::Result<()> {
+    if opt.modeline_defaults.is_some() {
+        print_sections(opt, std::io::stdin().lock(), writer)
+    } else if opt.watch.is_some() {
+        watch::run(opt, writer)
+    } else {
+        let input: Box<dyn BufRead> = match &opt.file[..] {
+            [] => input::open(None)?,
+            [file] => input::open(Some(file))?,
+            // Each file is opened when it's read, as its own series
+            _ => Box::new(std::io::empty()),
+        };
+        print_graph(opt, input, writer)
+    }
+}
+
+/// Print a graph for each section of the input
+///
+/// The first line of the input has already been read as the first modeline. Every line after it
+/// that's also a modeline starts a new section, with its own options and range.
+fn print_sections<W: Write>(
+    mut opt: Opt,
+    input: impl BufRead,
+    mut writer: LineWriter<W>,
+) -> anyhow::Result<()> {
+    // The first modeline has already been read, after any comments
+    let mut line_number = opt.line_offset + 1;
+    let mut section_start = line_number;
+    let options = input::ReadOptions {
+        line_number: line_number + 1,
+        ..opt.read_options()
+    };
+    let mut lines = input::lines(input, options);
+    let mut section = String::new();
+
+    loop {
+        let line = lines.next().transpose()?;
+        line_number += 1;
+
+        match line {
+            Some(line) if !Opt::is_modeline(&line) => {
+                section.push_str(&line);
+                section.push('\n');
+            }
+            line => {
+                let defaults = opt
+                    .modeline_defaults
+                    .take()
+                    .expect("should be kept from the previous modeline");
+
+                let input = Box::new(std::io::Cursor::new(std::mem::take(&mut section)));
+                opt.line_offset = section_start - 1;
+                print_graph(opt, input, LineWriter::new(&mut writer)).with_context(|| {
+                    format!("Failed to graph the section on line {section_start}")
+                })?;
+
+                let Some(line) = line else {
+                    break Ok(());
+                };
+
+                // Keep each graph apart from the one before it
+                writeln!(writer)?;
+
+                opt = Opt::try_from_modeline(defaults, &line)
+                    .with_context(|| format!("Invalid modeline on line {line_number}"))?;
+                section_start = line_number;
+            }
+        }
+    }
+}
+
+/// Print a single graph of the input, then list any lines that couldn't be parsed
+fn print_graph<W: Write>(
+    opt: Opt,
+    input: Box<dyn BufRead>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()> {
+    let skipped = std::rc::Rc::clone(&opt.skipped_lines);
+
+    print_graph_kind(opt, input, writer)?;
+
+    let skipped = skipped.borrow();
+    if !skipped.is_empty() {
+        eprintln!("{}", skipped_lines_summary(&skipped));
+    }
+
+    Ok(())
+}
+
+/// A warning listing the first few lines that couldn't be parsed
+fn skipped_lines_summary(lines: &[usize]) -> String {
+    const SHOWN: usize = 10;
+
+    let mut summary = format!(
+        "Warning: {} line{} couldn't be parsed: ",
+        lines.len(),
+        if lines.len() == 1 { "" } else { "s" }
+    );
+    summary.push_str(
+        &lines
+            .iter()
+            .take(SHOWN)
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+    );
+    if lines.len() > SHOWN {
+        summary.push_str(&format!(", and {} more", lines.len() - SHOWN));
+    }
+
+    summary
+}
+
+/// Print a graph of the input with the graph kind and options given
+fn print_graph_kind<W: Write>(
+    opt: Opt,
+    input: Box<dyn BufRead>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()> {
+    // Without a terminal to view it in, the graph is printed as usual
+    let interactive = opt.interactive && std::io::stdout().is_terminal();
+
     if opt.grid.is_some() {
-        grid::print_graph(opt, std::io::stdin().lock(), writer)
+        grid::print_graph(opt, input, writer)
     } else {
         match (opt.kind(), opt.per) {
-            (GraphKind::Bars, 1) => build_graph::<Option<f64>, BlockBars, W>(opt, writer),
-            (GraphKind::MiniBars, 1) => build_graph::<Option<f64>, MiniBlockLines, W>(opt, writer),
-            (GraphKind::MiniBars, 2) => {
-                build_graph::<[Option<f64>; 2], MiniBlockLines, W>(opt, writer)
+            (GraphKind::Columns, _) if interactive => {
+                pager::run::<BlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::MiniColumns, _) if interactive => {
+                pager::run::<MiniBlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleColumns, _) if interactive => {
+                pager::run::<BrailleColumns, W>(opt, input, writer)
+            }
+            (GraphKind::SextantColumns, _) if interactive => {
+                pager::run::<SextantColumns, W>(opt, input, writer)
             }
-            // (GraphKind::MiniBars, n) => build_graph::<Vec<Option<f64>>, MiniBlockLines, W>(opt, writer),
-            (GraphKind::Columns, 1) => build_graph::<Option<f64>, BlockColumns, W>(opt, writer),
-            (GraphKind::MiniColumns, 1) => {
-                build_graph::<Option<f64>, MiniBlockColumns, W>(opt, writer)
+            (GraphKind::OctantColumns, _) if interactive => {
+                pager::run::<OctantColumns, W>(opt, input, writer)
             }
-            (GraphKind::MiniColumns, 2) => {
-                build_graph::<[Option<f64>; 2], MiniBlockColumns, W>(opt, writer)
+            (GraphKind::Columns, _) if opt.tile.is_some() => {
+                tiles::print_tiles::<BlockColumns, W>(opt, input, writer)
             }
-            (GraphKind::BrailleBars, 1) => build_graph::<Option<f64>, BrailleLines, W>(opt, writer),
-            (GraphKind::BrailleBars, 2) => {
-                build_graph::<[Option<f64>; 2], BrailleLines, W>(opt, writer)
+            (GraphKind::MiniColumns, _) if opt.tile.is_some() => {
+                tiles::print_tiles::<MiniBlockColumns, W>(opt, input, writer)
             }
-            (GraphKind::BrailleColumns, 1) => {
-                build_graph::<Option<f64>, BrailleColumns, W>(opt, writer)
+            (GraphKind::BrailleColumns, _) if opt.tile.is_some() => {
+                tiles::print_tiles::<BrailleColumns, W>(opt, input, writer)
             }
-            (GraphKind::BrailleColumns, 2) => {
-                build_graph::<[Option<f64>; 2], BrailleColumns, W>(opt, writer)
+            (GraphKind::SextantColumns, _) if opt.tile.is_some() => {
+                tiles::print_tiles::<SextantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::OctantColumns, _) if opt.tile.is_some() => {
+                tiles::print_tiles::<OctantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::Columns, _) if opt.time => {
+                build_time_series_graph::<BlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::MiniColumns, _) if opt.time => {
+                build_time_series_graph::<MiniBlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleColumns, _) if opt.time => {
+                build_time_series_graph::<BrailleColumns, W>(opt, input, writer)
+            }
+            (GraphKind::SextantColumns, _) if opt.time => {
+                build_time_series_graph::<SextantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::OctantColumns, _) if opt.time => {
+                build_time_series_graph::<OctantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::Bars, _) if opt.group => {
+                build_grouped_graph::<BlockBars, W>(opt, input, writer)
+            }
+            (GraphKind::MiniBars, _) if opt.group => {
+                build_grouped_graph::<MiniBlockLines, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleBars, _) if opt.group => {
+                build_grouped_graph::<BrailleLines, W>(opt, input, writer)
+            }
+            (GraphKind::SextantBars, _) if opt.group => {
+                build_grouped_graph::<SextantBars, W>(opt, input, writer)
+            }
+            (GraphKind::OctantBars, _) if opt.group => {
+                build_grouped_graph::<OctantBars, W>(opt, input, writer)
+            }
+            (GraphKind::Bars, 1) if !opt.stack => {
+                build_graph::<Option<f64>, BlockBars, W>(opt, input, writer)
+            }
+            (GraphKind::Bars, _) => {
+                build_graph::<Vec<Option<f64>>, BlockBars, W>(opt, input, writer)
+            }
+            (GraphKind::MiniBars, 1) if !opt.stack => {
+                build_graph::<Option<f64>, MiniBlockLines, W>(opt, input, writer)
+            }
+            (GraphKind::MiniBars, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], MiniBlockLines, W>(opt, input, writer)
+            }
+            (GraphKind::MiniBars, _) => {
+                build_graph::<Vec<Option<f64>>, MiniBlockLines, W>(opt, input, writer)
+            }
+            (GraphKind::Columns, 1) if !opt.stack => {
+                build_graph::<Option<f64>, BlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::Columns, _) => {
+                build_graph::<Vec<Option<f64>>, BlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::MiniColumns, 1) if !opt.stack => {
+                build_graph::<Option<f64>, MiniBlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::MiniColumns, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], MiniBlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::MiniColumns, _) => {
+                build_graph::<Vec<Option<f64>>, MiniBlockColumns, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleBars, 1) if !opt.stack => {
+                build_graph::<Option<f64>, BrailleLines, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleBars, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], BrailleLines, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleBars, _) => {
+                build_graph::<Vec<Option<f64>>, BrailleLines, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleColumns, 1) if !opt.stack => {
+                build_graph::<Option<f64>, BrailleColumns, W>(opt, input, writer)
+            }
+            (GraphKind::BrailleColumns, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], BrailleColumns, W>(opt, input, writer)
             }
             (GraphKind::BrailleColumns, _) => {
-                build_graph::<Vec<Option<f64>>, BrailleColumns, W>(opt, writer)
+                build_graph::<Vec<Option<f64>>, BrailleColumns, W>(opt, input, writer)
             }
 
-            (GraphKind::SextantBars, 1) => build_graph::<Option<f64>, SextantBars, W>(opt, writer),
-            (GraphKind::SextantBars, 2) => {
-                build_graph::<[Option<f64>; 2], SextantBars, W>(opt, writer)
+            (GraphKind::SextantBars, 1) if !opt.stack => {
+                build_graph::<Option<f64>, SextantBars, W>(opt, input, writer)
             }
-            (GraphKind::SextantColumns, 1) => {
-                build_graph::<Option<f64>, SextantColumns, W>(opt, writer)
+            (GraphKind::SextantBars, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], SextantBars, W>(opt, input, writer)
             }
-            (GraphKind::SextantColumns, 2) => {
-                build_graph::<[Option<f64>; 2], SextantColumns, W>(opt, writer)
+            (GraphKind::SextantBars, _) => {
+                build_graph::<Vec<Option<f64>>, SextantBars, W>(opt, input, writer)
             }
-
-            (GraphKind::OctantBars, 1) => build_graph::<Option<f64>, OctantBars, W>(opt, writer),
-            (GraphKind::OctantBars, 2) => {
-                build_graph::<[Option<f64>; 2], OctantBars, W>(opt, writer)
+            (GraphKind::SextantColumns, 1) if !opt.stack => {
+                build_graph::<Option<f64>, SextantColumns, W>(opt, input, writer)
             }
-            (GraphKind::OctantColumns, 1) => {
-                build_graph::<Option<f64>, OctantColumns, W>(opt, writer)
+            (GraphKind::SextantColumns, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], SextantColumns, W>(opt, input, writer)
             }
-            (GraphKind::OctantColumns, 2) => {
-                build_graph::<[Option<f64>; 2], OctantColumns, W>(opt, writer)
+            (GraphKind::SextantColumns, _) => {
+                build_graph::<Vec<Option<f64>>, SextantColumns, W>(opt, input, writer)
             }
 
-            _ => todo!(),
+            (GraphKind::OctantBars, 1) if !opt.stack => {
+                build_graph::<Option<f64>, OctantBars, W>(opt, input, writer)
+            }
+            (GraphKind::OctantBars, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], OctantBars, W>(opt, input, writer)
+            }
+            (GraphKind::OctantBars, _) => {
+                build_graph::<Vec<Option<f64>>, OctantBars, W>(opt, input, writer)
+            }
+            (GraphKind::OctantColumns, 1) if !opt.stack => {
+                build_graph::<Option<f64>, OctantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::OctantColumns, 2) if !opt.stack => {
+                build_graph::<[Option<f64>; 2], OctantColumns, W>(opt, input, writer)
+            }
+            (GraphKind::OctantColumns, _) => {
+                build_graph::<Vec<Option<f64>>, OctantColumns, W>(opt, input, writer)
+            }
         }
     }
 }
 
-fn build_graph<LineType, Graph, W>(mut opt: Opt, writer: LineWriter<W>) -> anyhow::Result<()>
+fn build_graph<LineType, Graph, W>(
+    mut opt: Opt,
+    input: Box<dyn BufRead>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()>
 where
     LineType: 'static,
     Graph: Graphable<LineType>,
-    InputLine<LineType>: std::str::FromStr + for<'a> InputLineSinglable<'a>,
-    <InputLine<LineType> as std::str::FromStr>::Err: std::error::Error + Send + Sync,
+    InputLine<LineType>:
+        input::ParseLine + input::Gap + input::FromValues + for<'a> InputLineSinglable<'a>,
     W: Write,
+{
+    let lines = read_lines::<LineType>(&mut opt, input)?;
+
+    // A regular file can be read again from the start, so its lines don't need to be kept
+    let rewind = seekable_file(&opt).map(|path| {
+        let path = path.to_path_buf();
+        let mut opt = opt.clone();
+        // Lines that are skipped were already counted the first time
+        opt.skipped_lines = std::rc::Rc::default();
+        Box::new(move || read_lines::<LineType>(&mut opt, input::open(Some(&path))?))
+            as Rewind<LineType>
+    });
+
+    print_lines::<LineType, Graph, W>(opt, lines, rewind, writer)
+}
+
+/// The file being graphed, if it's a regular file that can be read more than once
+fn seekable_file(opt: &Opt) -> Option<&std::path::Path> {
+    match &opt.file[..] {
+        [path] if path.as_os_str() != "-" => std::fs::metadata(path)
+            .is_ok_and(|metadata| metadata.is_file())
+            .then_some(path.as_path()),
+        _ => None,
+    }
+}
+
+/// Build a bar graph with each value of a line on its own row, grouped by line
+fn build_grouped_graph<Graph, W>(
+    mut opt: Opt,
+    input: Box<dyn BufRead>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()>
+where
+    Graph: Graphable<Option<f64>>,
+    W: Write,
+{
+    let lines = read_lines::<Vec<Option<f64>>>(&mut opt, input)?.into_grouped(usize::from(opt.per));
+
+    print_lines::<Option<f64>, Graph, W>(opt, lines, None, writer)
+}
+
+/// Build a column graph spanning the terminal, with values placed by their timestamps
+fn build_time_series_graph<Graph, W>(
+    mut opt: Opt,
+    input: Box<dyn BufRead>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()>
+where
+    Graph: Graphable<Option<f64>>,
+    W: Write,
+{
+    let width = util::get_terminal_size()?.0;
+    let count = width * opt.kind().char_type().values_per_column();
+
+    let (lines, axis) = read_lines::<(time::Timestamp, Option<f64>)>(&mut opt, input)?
+        .into_time_series(usize::from(count), opt.aggregate)?;
+    opt.time_axis = axis;
+
+    print_lines::<Option<f64>, Graph, W>(opt, lines, None, writer)
+}
+
+/// Read lines from the input, taking the names for the legend from the first line if needed
+fn read_lines<LineType>(
+    opt: &mut Opt,
+    input: Box<dyn BufRead>,
+) -> anyhow::Result<InputLines<LineType>>
+where
+    LineType: 'static,
+    InputLine<LineType>: input::ParseLine + input::Gap + input::FromValues,
 {
     let first_value = match opt.first_line {
         Some(FirstLine::Value(ref value)) => Some(value.trim().to_string()),
         _ => None,
     };
 
-    let lines = InputLines::<LineType>::try_from_path(first_value, opt.file.as_deref())?;
+    let options = opt.read_options();
+
+    if opt.file.len() > 1 {
+        return Ok(InputLines::<LineType>::from_files(&opt.file, &options)?);
+    }
+
+    if opt.header {
+        let (header, lines) =
+            InputLines::<LineType>::from_buf_reader_with_header(first_value, input, options)?;
+
+        opt.legend = header
+            .unwrap_or_default()
+            .split(|c: char| c == ',' || c.is_ascii_whitespace())
+            .filter(|name| !name.is_empty())
+            .map(String::from)
+            .collect();
+
+        Ok(lines)
+    } else {
+        Ok(InputLines::<LineType>::from_buf_reader(
+            first_value,
+            input,
+            options,
+        ))
+    }
+}
+
+fn print_lines<LineType, Graph, W>(
+    mut opt: Opt,
+    lines: InputLines<LineType>,
+    rewind: Option<Rewind<LineType>>,
+    writer: LineWriter<W>,
+) -> anyhow::Result<()>
+where
+    LineType: 'static,
+    Graph: Graphable<LineType>,
+    InputLine<LineType>: std::str::FromStr + for<'a> InputLineSinglable<'a>,
+    <InputLine<LineType> as std::str::FromStr>::Err: std::error::Error + Send + Sync,
+    W: Write,
+{
+    let values = opt.get_iter(lines, rewind)?;
+
+    // Every value along the x axis, to find the ones to label
+    let points: Option<Vec<(usize, f64)>> = match &values {
+        ValueIter::Bounded { lines } if opt.annotate => Some(
+            lines
+                .iter()
+                .enumerate()
+                .filter_map(|(index, line)| line.as_ref().ok().map(|line| (index, line)))
+                .flat_map(|(index, line)| {
+                    line.as_single_iter()
+                        .flatten()
+                        .map(move |value| (index, *value))
+                })
+                .collect(),
+        ),
+        _ => None,
+    };
+    let char_type = opt.kind().char_type();
+
+    // Keep the title and legend within the width of bar graphs, or the terminal for columns
+    let width = usize::from(match opt.kind().orientation() {
+        Orientation::Horizontal => opt.size.unwrap_or_default(),
+        Orientation::Vertical => util::get_terminal_size()?.0,
+    });
+
+    let title = opt
+        .title
+        .as_deref()
+        .map(|title| legend::truncate(title, width));
+    let footer = if opt.has_legend() {
+        let samples = opt.stack.then(|| {
+            let (series, orientation) = (usize::from(opt.per), opt.kind().orientation());
+            match opt.kind().char_type() {
+                CharType::Octant => legend::stack_samples::<Octants, 4>(series, orientation),
+                CharType::Sextant => legend::stack_samples::<Sextants, 3>(series, orientation),
+                CharType::HalfBlock => legend::stack_samples::<Quadrants, 2>(series, orientation),
+                CharType::Braille => legend::stack_samples::<Braille, 4>(series, orientation),
+                CharType::Block | CharType::Column => legend::stack_shade_samples(series),
+            }
+        });
+        legend::footer(
+            &opt.legend,
+            &opt.series_stats,
+            samples.as_deref(),
+            opt.unit.get(),
+            width,
+        )
+    } else {
+        vec![]
+    };
+
+    let axis = opt.time_axis.map(|axis| axis.labels(width));
+    let unit = opt.unit.get();
 
-    let values = opt.get_iter(lines)?;
     let config = Config::from(opt);
 
-    Graph::from(config).print_graph::<W>(values, writer)?;
+    let mut writer = writer;
+    if let Some(title) = title {
+        writeln!(writer, "{title}")?;
+    }
+
+    if let Some(points) = points {
+        let range = (config.minimum(), config.maximum());
+        let mut buffer = vec![];
+        Graph::from(config).print_graph(values, LineWriter::new(&mut buffer))?;
+
+        let graph = String::from_utf8(buffer)?;
+        let lines: Vec<&str> = graph.lines().collect();
+        let marks = annotate::column_marks(
+            &lines,
+            &points,
+            usize::from(char_type.values_per_column()),
+            usize::from(char_type.steps_per_row()),
+            range,
+            unit,
+        );
+        for line in annotate::label(&lines, &marks, width) {
+            writeln!(writer, "{line}")?;
+        }
+    } else {
+        Graph::from(config).print_graph(values, LineWriter::new(&mut writer))?;
+    }
+
+    if let Some(axis) = axis {
+        writeln!(writer, "{axis}")?;
+    }
+
+    for line in footer {
+        writeln!(writer, "{line}")?;
+    }
 
     Ok(())
 }
@@ -108,6 +537,20 @@ where
 mod tests {
     use super::*;
 
+    #[test]
+    fn summarize_skipped_lines() {
+        assert_eq!(
+            "Warning: 1 line couldn't be parsed: 7",
+            skipped_lines_summary(&[7])
+        );
+
+        let lines: Vec<usize> = (1..=12).collect();
+        assert_eq!(
+            "Warning: 12 lines couldn't be parsed: 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, and 2 more",
+            skipped_lines_summary(&lines)
+        );
+    }
+
     #[test]
     fn large_value_at_end_full_test() {
         let mut buffer = vec![];
@@ -1,6 +1,19 @@
+use crate::config_file::{self, ConfigFile};
+use crate::grid::Expression;
+use crate::input::{Format, Pattern, ReadOptions};
+use crate::legend::SeriesStats;
+use crate::quantile::Quantile;
+use crate::time::Axis;
+use crate::units::{self, Unit};
 use crate::util;
 use crate::{InputLine, InputLineSinglable, InputLines, LineResult};
 use clap::{Command, Parser, ValueEnum, builder::BoolishValueParser};
+use regex::Regex;
+use std::cell::{Cell, RefCell};
+use std::ffi::OsString;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::Duration;
 use std::{fmt, str::FromStr};
 
 #[derive(Debug, Copy, Clone, Default)]
@@ -76,6 +89,123 @@ impl FromStr for GraphRange {
     }
 }
 
+/// A value to draw a reference line at, with an optional label
+#[derive(Debug, Clone)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct ReferenceLine {
+    pub value: f64,
+    pub label: Option<String>,
+}
+
+impl FromStr for ReferenceLine {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (value, label) = match s.split_once(':') {
+            Some((value, label)) => (value, Some(label.to_string())),
+            None => (s, None),
+        };
+
+        Ok(Self {
+            value: value.parse()?,
+            label: label.filter(|label| !label.is_empty()),
+        })
+    }
+}
+
+/// Where filled graphs are filled from
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(test, derive(PartialEq))]
+pub enum Baseline {
+    Value(f64),
+    Mean,
+    Median,
+    Min,
+}
+
+impl Baseline {
+    /// What to keep of the values to find the baseline from them, if they're needed
+    ///
+    /// The median is exact when `every_value` is kept anyway, and estimated otherwise.
+    fn values(self, every_value: bool) -> Option<BaselineValues> {
+        match self {
+            Self::Mean => Some(BaselineValues::Sum {
+                total: 0.,
+                count: 0,
+            }),
+            Self::Median if every_value => Some(BaselineValues::Every(vec![])),
+            Self::Median => Some(BaselineValues::Sketch(Quantile::new(0.5))),
+            Self::Value(_) | Self::Min => None,
+        }
+    }
+}
+
+/// What's kept of the values to find the baseline from them
+#[derive(Debug)]
+enum BaselineValues {
+    /// A running total, for the mean
+    Sum { total: f64, count: usize },
+
+    /// Every value, for the exact median
+    Every(Vec<f64>),
+
+    /// An estimate of the median, without keeping the values
+    Sketch(Quantile),
+}
+
+impl BaselineValues {
+    fn push(&mut self, value: f64) {
+        match self {
+            Self::Sum { total, count } => {
+                *total += value;
+                *count += 1;
+            }
+            Self::Every(values) => values.push(value),
+            Self::Sketch(quantile) => quantile.push(value),
+        }
+    }
+
+    /// The baseline found from the values, or zero if there weren't any
+    fn resolve(self) -> Baseline {
+        let baseline = match self {
+            Self::Sum { count: 0, .. } => None,
+            Self::Sum { total, count } => {
+                #[allow(clippy::cast_precision_loss)]
+                let count = count as f64;
+                Some(total / count)
+            }
+            Self::Every(mut values) if !values.is_empty() => {
+                values.sort_by(f64::total_cmp);
+                let middle = values.len() / 2;
+                if values.len().is_multiple_of(2) {
+                    Some(f64::midpoint(values[middle - 1], values[middle]))
+                } else {
+                    Some(values[middle])
+                }
+            }
+            Self::Every(_) => None,
+            Self::Sketch(quantile) => quantile.estimate(),
+        };
+
+        Baseline::Value(baseline.unwrap_or(0.))
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "mean" => Ok(Self::Mean),
+            "median" => Ok(Self::Median),
+            "min" => Ok(Self::Min),
+            _ => s.parse().map(Self::Value).map_err(|_| {
+                anyhow::anyhow!("Baseline should be a number, \"mean\", \"median\", or \"min\"")
+            }),
+        }
+    }
+}
+
 /// Fill the space between series when the first is less or equal to the second value (or zero
 /// if `--per` is 1), and hollow otherwise
 ///
@@ -214,7 +344,175 @@ pub enum GraphStyle {
     Filled,
 }
 
-#[derive(Debug, Parser)]
+/// The unit of the angles read with --polar
+#[derive(Debug, Default, ValueEnum, Clone, Copy)]
+pub enum AngleUnit {
+    #[default]
+    Radians,
+
+    Degrees,
+}
+
+/// What's done with lines of input that can't be parsed
+#[derive(Debug, Default, ValueEnum, Clone, Copy)]
+pub enum OnError {
+    /// Leave the line out of the graph
+    Skip,
+
+    /// Draw the line as a gap, like a missing value
+    Null,
+
+    /// Stop with an error
+    #[default]
+    Fail,
+}
+
+/// What's done with lines that --pattern doesn't match
+#[derive(Debug, Default, ValueEnum, Clone, Copy)]
+pub enum Unmatched {
+    /// Leave the line out of the graph
+    #[default]
+    Skip,
+
+    /// Draw the line as a gap, like a missing value
+    Gap,
+}
+
+/// What's drawn for values that are NaN or infinite
+#[derive(Debug, Default, ValueEnum, Clone, Copy, PartialEq, Eq)]
+pub enum NonFinite {
+    /// Draw them as gaps, like missing values
+    #[default]
+    Gap,
+
+    /// Draw infinite values at the top or bottom of the graph (NaN is still a gap)
+    Clip,
+}
+
+/// How values drawn in the same column are combined
+#[derive(Debug, Default, ValueEnum, Clone, Copy)]
+pub enum Aggregate {
+    /// The smallest value
+    Min,
+
+    /// The largest value
+    Max,
+
+    /// The mean of the values
+    #[default]
+    Avg,
+}
+
+impl Aggregate {
+    /// Combine values into one, if there are any
+    #[must_use]
+    pub fn combine(self, values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        let mut values = values.into_iter();
+        let first = values.next()?;
+        let (min, max, sum, count) =
+            values.fold((first, first, first, 1), |(min, max, sum, count), value| {
+                (min.min(value), max.max(value), sum + value, count + 1)
+            });
+
+        Some(match self {
+            Self::Min => min,
+            Self::Max => max,
+            Self::Avg => sum / f64::from(count),
+        })
+    }
+}
+
+/// How many rows and columns graphs are tiled in
+///
+/// Either can be left out to fit every series.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(test, derive(PartialEq))]
+pub struct Tiling {
+    rows: Option<usize>,
+    columns: Option<usize>,
+}
+
+impl Tiling {
+    /// The rows and columns of tiles needed for `count` series, as close to square as possible
+    /// when neither is given
+    pub fn layout(self, count: usize) -> anyhow::Result<(usize, usize)> {
+        let count = count.max(1);
+        let (rows, columns) = match (self.rows, self.columns) {
+            (Some(rows), Some(columns)) => (rows, columns),
+            (Some(rows), None) => (rows, count.div_ceil(rows)),
+            (None, Some(columns)) => (count.div_ceil(columns), columns),
+            (None, None) => {
+                let root = count.isqrt();
+                let columns = root + usize::from(root * root < count);
+                (count.div_ceil(columns), columns)
+            }
+        };
+
+        anyhow::ensure!(
+            rows * columns >= count,
+            "{count} series don't fit in {rows}x{columns} tiles"
+        );
+
+        Ok((rows, columns))
+    }
+}
+
+impl FromStr for Tiling {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((rows, columns)) = s.split_once('x') else {
+            anyhow::bail!("Tiling should be [ROWS]x[COLUMNS], e.g. 2x3");
+        };
+
+        let parse = |value: &str| -> anyhow::Result<Option<usize>> {
+            match value {
+                "" => Ok(None),
+                value => match value.parse()? {
+                    0 => anyhow::bail!("Tiles need at least one row and column"),
+                    value => Ok(Some(value)),
+                },
+            }
+        };
+
+        Ok(Self {
+            rows: parse(rows)?,
+            columns: parse(columns)?,
+        })
+    }
+}
+
+/// How long to wait between runs of the command given to --watch
+#[derive(Debug, Clone, Copy)]
+pub struct Interval(pub Duration);
+
+impl FromStr for Interval {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let seconds = match units::parse(s.trim()) {
+            Some((seconds, None | Some(Unit::Seconds))) => seconds,
+            _ => anyhow::bail!("Interval should be a number of seconds, or a duration like 500ms"),
+        };
+
+        Duration::try_from_secs_f64(seconds)
+            .map(Self)
+            .map_err(|_| anyhow::anyhow!("Interval can't be negative"))
+    }
+}
+
+/// Which range each tile is drawn with
+#[derive(Debug, Default, ValueEnum, Clone, Copy)]
+pub enum TileRange {
+    /// Every tile has the same range, fit to every series
+    #[default]
+    Shared,
+
+    /// Each tile has its own range, fit to its series
+    Independent,
+}
+
+#[derive(Debug, Clone, Parser)]
 #[command(version)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct Opt {
@@ -244,18 +542,175 @@ pub struct Opt {
     /// Number of values per line of input
     ///
     /// When the graph kind supports it, each value represents a separate series.
-    #[arg(short, long, default_value_t = 1, value_parser(clap::value_parser!(u8).range(1..)))]
+    #[arg(
+        short,
+        long,
+        env = "BRAILLE_PER",
+        default_value_t = 1,
+        value_parser(clap::value_parser!(u8).range(1..))
+    )]
     pub per: u8,
 
     /// How the space between multiple series should be handled
-    #[arg(short, long, value_enum, default_value_t)]
+    #[arg(short, long, env = "BRAILLE_STYLE", value_enum, default_value_t)]
     pub style: GraphStyle,
 
-    /// Determine the dimensions of the braille grid in dots (width and height)
+    /// Stack each series on top of the running total of the ones before it
+    ///
+    /// Each series is filled with its own dot pattern (solid, every other dot, every third dot,
+    /// and so on), and the range is determined from the stacked totals. The block bar and column
+    /// kinds fill each series with its own shade instead, rounding each total to a whole block.
+    #[arg(long)]
+    pub stack: bool,
+
+    /// Draw the values of each line as adjacent bars, with a gap between each line
+    ///
+    /// Each value gets its own row (one dot tall for the dot based kinds, one character tall for
+    /// block bars), so series with similar values can still be compared. Only supported by the
+    /// bar kinds.
+    #[arg(long, conflicts_with = "stack")]
+    pub group: bool,
+
+    /// Scale each series to its own range, so series of very different sizes can be compared
+    ///
+    /// Each series spans the full height (or width) of the graph, and --range gives the bounds of
+    /// every series. Reference lines are drawn on the scale of the first series. The legend
+    /// shows each series' own minimum and maximum, and --annotate isn't supported.
+    #[arg(long, conflicts_with_all = ["stack", "group", "baseline", "tile"])]
+    pub independent_scale: bool,
+
+    /// The value filled graphs are filled from (defaults to zero)
+    ///
+    /// Either a number, or one of `mean`, `median`, or `min` to use that of the input. The auto
+    /// style fills values above the baseline and leaves values below it hollow. Block graphs
+    /// can only start from a full block, so the baseline is rounded to the nearest one, and they
+    /// fill from the minimum if this isn't given. Graphs of a file are read twice instead of
+    /// being kept in memory, so their median is estimated. Not supported with two values per
+    /// line, which are drawn as the span between them.
+    #[arg(
+        long,
+        value_name = "VALUE|mean|median|min",
+        allow_hyphen_values = true,
+        conflicts_with = "stack"
+    )]
+    pub baseline: Option<Baseline>,
+
+    /// Draw a dashed horizontal line across a column graph at this value
+    ///
+    /// Can be given more than once. A label can be added after a colon (e.g. `--hline 250:SLO`),
+    /// which is printed at the end of the row the line is drawn in. The range is widened to
+    /// include the line if it's determined automatically; otherwise lines outside it are skipped.
+    #[arg(long, value_name = "VALUE[:LABEL]", allow_hyphen_values = true)]
+    pub hline: Vec<ReferenceLine>,
+
+    /// Draw a dashed vertical line down a bar graph at this value
+    ///
+    /// Can be given more than once. A label can be added after a colon (e.g. `--vline 250:SLO`),
+    /// which is printed above the graph where the line is drawn. The range is widened to include
+    /// the line if it's determined automatically; otherwise lines outside it are skipped.
+    #[arg(long, value_name = "VALUE[:LABEL]", allow_hyphen_values = true)]
+    pub vline: Vec<ReferenceLine>,
+
+    /// Print a title above the graph
+    #[arg(long)]
+    pub title: Option<String>,
+
+    /// Print a legend below the graph, naming each series in order (comma separated)
+    ///
+    /// Each series gets its own line with its minimum, maximum, and last value. Stacked graphs
+    /// also show a sample of how each series is filled.
+    #[arg(long, value_name = "NAME,...", value_delimiter = ',')]
+    pub legend: Vec<String>,
+
+    /// Use the first line of input as the names in the legend
+    ///
+    /// Names can be separated by commas or whitespace. Implies --legend.
+    #[arg(long, conflicts_with = "legend")]
+    pub header: bool,
+
+    /// Read the first field of each line as a timestamp, and place values along the x axis by time
+    ///
+    /// Timestamps are either RFC 3339 (e.g. `2024-05-01T13:45:00Z`, or just the date) or seconds
+    /// since the Unix epoch. The graph spans the width of the terminal, with samples in the same
+    /// column combined by --aggregate, and gaps left for columns without any. Times are labeled
+    /// below the graph in UTC. Only supported by the column kinds.
+    #[arg(long, conflicts_with_all = ["stack", "group"])]
+    pub time: bool,
+
+    /// How values in the same column are combined with --time, --tile, or --interactive
+    #[arg(long, value_enum, default_value_t)]
+    pub aggregate: Aggregate,
+
+    /// Draw each series as its own column graph, tiled in rows and columns across the terminal
+    ///
+    /// Either the rows or columns can be left out (e.g. `--tile x4`), or both (`--tile`) to tile
+    /// as close to a square as possible. Each line has a value for every series, separated by
+    /// whitespace or commas, and each tile is named after its series from --legend or --header.
+    /// Series with more values than fit in their tile have neighbouring values combined by
+    /// --aggregate. Only supported by the column kinds.
+    #[arg(
+        long,
+        value_name = "[ROWS]x[COLUMNS]",
+        num_args = 0..=1,
+        default_missing_value = "x",
+        conflicts_with_all = ["stack", "group", "time", "baseline"]
+    )]
+    pub tile: Option<Tiling>,
+
+    /// Whether tiles share the same range, or each have their own
+    #[arg(long, value_enum, default_value_t)]
+    pub tile_range: TileRange,
+
+    /// Label the smallest, largest, and last values where they're drawn in the graph
+    ///
+    /// Each label is an arrow pointing at its value, placed in empty space next to it so it
+    /// doesn't cover anything drawn. Values without room for a label are left without one. In
+    /// --interactive mode, this labels the values in view, and can be toggled with `a`. Grids
+    /// label the points with the smallest and largest y values, and the last point. Only
+    /// supported by the column kinds and grids, and not with --independent-scale. Every line is
+    /// kept in memory to place the labels, even when reading a file.
+    #[arg(long, conflicts_with_all = ["stack", "tile"])]
+    pub annotate: bool,
+
+    /// View the graph full screen, panning and zooming along the x axis with the keyboard
+    ///
+    /// Use the arrow keys (or `h` and `l`) to move the cursor, which shows the value under it,
+    /// `PageUp` and `PageDown` (or `H` and `L`) to scroll a screen at a time, `Home` and `End` (or
+    /// `g` and `G`) to jump to either end, `+` and `-` to zoom in and out, `a` to toggle
+    /// --annotate, and `q` to quit. When zoomed out, neighbouring values are combined by
+    /// --aggregate. The range is fit to the values in view, unless given by --range. The graph is
+    /// printed as usual when standard output isn't a terminal. Only supported by the column
+    /// kinds, with one value per line.
+    #[arg(
+        short,
+        long,
+        conflicts_with_all = ["stack", "group", "time", "tile", "legend", "header", "grid", "watch"]
+    )]
+    pub interactive: bool,
+
+    /// Run a command every interval, and graph what it prints as it goes, like `watch`
+    ///
+    /// The command is given after `--`, and each line it prints is added to the end of the
+    /// input. Only the lines that fit across the graph (or down it, for the bar kinds) are kept.
+    /// The interval is in seconds, unless written with a unit like `500ms` or `1m`. Press `q` to
+    /// quit. When standard output isn't a terminal, each graph is printed after the one before
+    /// it, separated by an empty line. Watching stops if the command fails.
+    ///
+    /// e.g. `braille --watch 2s -- sh -c 'cut -d" " -f1 /proc/loadavg'`
+    #[arg(
+        long,
+        value_name = "INTERVAL",
+        requires = "command",
+        conflicts_with_all = ["file", "header"]
+    )]
+    pub watch: Option<Interval>,
+
+    /// Determine the dimensions of the grid in dots (width and height)
     ///
     /// Passing no arguments means the smaller of either width or height will be used (to make it
     /// square without cropping or stretching). Respects --use-full-default-height. If one value is
-    /// passed, it's interpreted as both width and height.
+    /// passed, it's interpreted as both width and height. The dots are drawn with the characters
+    /// of the octant, sextant, or mini kinds when one is given, and with braille otherwise.
     #[arg(short, num_args(0..=2))]
     pub grid: Option<Vec<u16>>,
 
@@ -271,6 +726,38 @@ pub struct Opt {
     #[arg(short, long, allow_hyphen_values = true)]
     pub y_bounds: Option<GraphRange>,
 
+    /// Read each line of a grid as an angle and a radius, instead of x and y
+    ///
+    /// The angle is in radians unless `degrees` is given, and goes counterclockwise from the
+    /// positive x axis. Only supported by grids.
+    #[arg(
+        long,
+        value_enum,
+        value_name = "UNIT",
+        num_args = 0..=1,
+        default_missing_value = "radians",
+        requires = "grid"
+    )]
+    pub polar: Option<AngleUnit>,
+
+    /// Read each line of a grid as a value of `t`, and plot the point where x and y are these
+    /// expressions of it
+    ///
+    /// Expressions are made of numbers, `t`, `pi`, `e`, the operators `+ - * / % ^`, parentheses,
+    /// and the functions `sin`, `cos`, `tan`, `asin`, `acos`, `atan`, `sqrt`, `abs`, `exp`, `ln`,
+    /// `log`, `floor`, and `ceil`. With --polar, they give the angle and radius instead. Points
+    /// where either expression isn't a finite number are left out. Only supported by grids.
+    ///
+    /// e.g. `seq 0 0.01 6.29 | braille -g --parametric 'sin(3*t)' 'sin(2*t)'`
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["X", "Y"],
+        allow_hyphen_values = true,
+        requires = "grid"
+    )]
+    pub parametric: Option<Vec<Expression>>,
+
     /// Interpret arguments from the very first line of the input
     ///
     /// If this is passed, then the first line from standard input should match the following:
@@ -283,6 +770,9 @@ pub struct Opt {
     /// Where `OPTIONS` and `ARGUMENTS` are space separated values as you would pass them on the
     /// command line, and `VALUES` are the values you want to graph.
     ///
+    /// Any later line starting with "braille" is also a modeline, and starts a new graph with its
+    /// own options and range. Each graph is separated by an empty line.
+    ///
     /// # Example
     ///
     /// `input.txt`
@@ -319,6 +809,7 @@ pub struct Opt {
             "file",
             "kind",
             "size",
+            "watch",
         ],
         verbatim_doc_comment
     )]
@@ -359,8 +850,72 @@ pub struct Opt {
     pub braille_columns: bool,
 
     /// Path to file to read from (defaults to standard input)
+    ///
+    /// Give more than once to read each file as its own series, side by side, with shorter files
+    /// padded with gaps. `-` reads standard input. This sets --per to the number of files.
     #[arg(short, long, conflicts_with = "modeline")]
-    pub file: Option<std::path::PathBuf>,
+    pub file: Vec<std::path::PathBuf>,
+
+    /// Replace invalid UTF-8 in the input with `�` instead of stopping with an error
+    #[arg(long)]
+    pub lossy: bool,
+
+    /// What to do with lines of input that can't be parsed
+    ///
+    /// When lines are skipped or drawn as gaps, their line numbers are listed on standard error
+    /// after the graph. Lines of a time series can't be drawn as gaps, so they're skipped.
+    #[arg(long, value_enum, default_value_t, value_name = "ACTION")]
+    pub on_error: OnError,
+
+    /// Values that are drawn as gaps, besides empty ones, separated by commas
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "null",
+        value_name = "VALUES"
+    )]
+    pub null_values: Vec<String>,
+
+    /// What to draw for values that are NaN or infinite
+    ///
+    /// Infinite values are left out when finding the range of the graph, so clipping them
+    /// draws them at the edge of the range that fits the rest of the values.
+    #[arg(long, value_enum, default_value_t, value_name = "ACTION")]
+    pub non_finite: NonFinite,
+
+    /// Skip lines of input starting with this, or none if it's empty
+    ///
+    /// With --modeline, comments before the modeline are skipped too.
+    #[arg(long, default_value = "#", value_name = "PREFIX")]
+    pub comment_prefix: String,
+
+    /// Read values with suffixes and units, like `12k`, `3.4MiB`, `250ms`, or `45%`
+    ///
+    /// SI (`k`, `M`, `G`, ...) and IEC (`Ki`, `Mi`, `Gi`, ...) prefixes multiply the value, sizes
+    /// are read in bytes, and durations (`ns`, `us`, `ms`, `s`, `m`, `h`, `d`) in seconds. Values
+    /// in the legend and annotations are written in the same unit. Every value with a unit must
+    /// have the same kind of unit.
+    #[arg(long)]
+    pub units: bool,
+
+    /// Pick the values out of each line with a regular expression
+    ///
+    /// Each capture group is a value, in order, or only the named groups if there are any (which
+    /// also name the series in the legend if --legend isn't given). Without any groups, the whole
+    /// match is the value. There must be as many values as --per (plus the timestamp with
+    /// --time), or two for --grid.
+    #[arg(long, value_name = "REGEX")]
+    pub pattern: Option<Regex>,
+
+    /// What to do with lines that --pattern doesn't match
+    #[arg(
+        long,
+        value_enum,
+        default_value_t,
+        value_name = "ACTION",
+        requires = "pattern"
+    )]
+    pub unmatched: Unmatched,
 
     /// Use the full height if none given
     ///
@@ -370,15 +925,91 @@ pub struct Opt {
     #[arg(long, env = "BRAILLE_USE_FULL_DEFAULT_HEIGHT", value_parser = BoolishValueParser::new())]
     pub use_full_default_height: bool,
 
+    /// Read defaults and presets from this file instead of the user config file
+    ///
+    /// The user config file is `$XDG_CONFIG_HOME/braille/config.toml` (or
+    /// `~/.config/braille/config.toml`). The nearest `.braille.toml` in the current directory or
+    /// its parents is read after it, and takes precedence. Keys are long option names, with
+    /// presets as tables under `preset`:
+    ///
+    /// ```toml
+    /// style = "filled"
+    ///
+    /// [preset.latency]
+    /// kind = "braille-columns"
+    /// range = "0:"
+    /// hline = ["250:SLO"]
+    /// ```
+    ///
+    /// Options from the command line take precedence over config files, as do --per, --style, and
+    /// --use-full-default-height when given by their environment variables.
+    #[arg(
+        long,
+        env = "BRAILLE_CONFIG",
+        value_name = "PATH",
+        verbatim_doc_comment
+    )]
+    pub config: Option<PathBuf>,
+
+    /// Use the options from a preset in the config files
+    ///
+    /// Presets take precedence over the defaults in any config file.
+    #[arg(long, env = "BRAILLE_PRESET", value_name = "NAME")]
+    pub preset: Option<String>,
+
     /// How wide or tall the graph can be (defaults to terminal size)
     #[arg(value_parser = clap::value_parser!(u16).range(1..))]
     pub size: Option<u16>,
 
+    /// The command to run with --watch
+    #[arg(last = true, value_name = "COMMAND", requires = "watch")]
+    pub command: Vec<OsString>,
+
     #[arg(skip)]
     pub first_line: Option<FirstLine>,
+
+    /// Statistics of each series, collected when a legend is printed
+    #[arg(skip)]
+    pub series_stats: Vec<Option<SeriesStats>>,
+
+    /// The span of time along the x axis of a time series
+    #[arg(skip)]
+    pub time_axis: Option<Axis>,
+
+    /// Kept from the first modeline to parse the modelines of later sections
+    #[arg(skip)]
+    pub modeline_defaults: Option<ModelineDefaults>,
+
+    /// Numbers of the lines that couldn't be parsed, but didn't stop the graph
+    #[arg(skip)]
+    pub skipped_lines: Rc<RefCell<Vec<usize>>>,
+
+    /// The unit of the values read so far, if they're read with --units and have one
+    #[arg(skip)]
+    pub unit: Rc<Cell<Option<Unit>>>,
+
+    /// How many lines of the input came before the ones being graphed, when it's split into
+    /// sections by modelines
+    #[arg(skip)]
+    pub line_offset: usize,
 }
 
-#[derive(Debug)]
+/// What modelines are parsed with, besides the modeline itself
+#[derive(Debug, Clone)]
+pub struct ModelineDefaults {
+    config_files: Vec<ConfigFile>,
+    preset: Option<String>,
+    use_full_default_height: bool,
+}
+
+impl ModelineDefaults {
+    /// Insert the options from the config files before the arguments from a modeline
+    fn insert_config_args(&self, cmd: &Command, args: &mut Vec<OsString>) -> anyhow::Result<()> {
+        Opt::insert_config_args(cmd, &self.config_files, args, 0, self.preset.as_deref())
+    }
+}
+
+#[derive(Debug, Clone)]
 pub enum FirstLine {
     ModeLine,
     Value(String),
@@ -387,15 +1018,21 @@ pub enum FirstLine {
 pub trait Configurable: From<Opt> {
     fn kind(&self) -> GraphKind;
     fn style(&self) -> GraphStyle;
+    fn stack(&self) -> bool;
+    fn references(&self) -> &[ReferenceLine];
+    fn baseline(&self) -> Option<f64>;
     fn minimum(&self) -> f64;
     fn maximum(&self) -> f64;
     fn size(&self) -> u16;
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Config {
     kind: GraphKind,
     style: GraphStyle,
+    stack: bool,
+    references: Vec<ReferenceLine>,
+    baseline: Option<f64>,
     minimum: f64,
     maximum: f64,
     size: u16,
@@ -404,9 +1041,27 @@ pub struct Config {
 impl From<Opt> for Config {
     fn from(value: Opt) -> Self {
         if let (Some(min), Some(max)) = (value.range.min(), value.range.max()) {
+            let kind = value.kind();
+            let references = match kind.orientation() {
+                Orientation::Horizontal => value.vline,
+                Orientation::Vertical => value.hline,
+            };
+
             Self {
-                kind: value.kind(),
+                kind,
                 style: value.style,
+                stack: value.stack,
+                references: references
+                    .into_iter()
+                    .filter(|reference| (min..=max).contains(&reference.value))
+                    .collect(),
+                baseline: value.baseline.map(|baseline| match baseline {
+                    Baseline::Value(baseline) => baseline,
+                    Baseline::Min => min,
+                    Baseline::Mean | Baseline::Median => {
+                        unreachable!("The baseline should already have been calculated")
+                    }
+                }),
                 minimum: min,
                 maximum: max,
                 size: value.size.unwrap(),
@@ -417,6 +1072,25 @@ impl From<Opt> for Config {
     }
 }
 
+impl Config {
+    /// The same settings for a graph with a different range and size, like a tile
+    #[must_use]
+    pub fn resized(&self, minimum: f64, maximum: f64, size: u16) -> Self {
+        Self {
+            references: self
+                .references
+                .iter()
+                .filter(|reference| (minimum..=maximum).contains(&reference.value))
+                .cloned()
+                .collect(),
+            minimum,
+            maximum,
+            size,
+            ..self.clone()
+        }
+    }
+}
+
 impl Configurable for Config {
     fn kind(&self) -> GraphKind {
         self.kind
@@ -426,6 +1100,18 @@ impl Configurable for Config {
         self.style
     }
 
+    fn stack(&self) -> bool {
+        self.stack
+    }
+
+    fn references(&self) -> &[ReferenceLine] {
+        &self.references
+    }
+
+    fn baseline(&self) -> Option<f64> {
+        self.baseline
+    }
+
     fn minimum(&self) -> f64 {
         self.minimum
     }
@@ -460,19 +1146,40 @@ impl Opt {
         I: IntoIterator<Item = S>,
         S: Into<std::ffi::OsString> + Clone,
     {
+        use clap::{CommandFactory, FromArgMatches};
+
+        let mut args: Vec<OsString> = args.into_iter().map(Into::into).collect();
+        let mut cmd = Self::command();
+        cmd.build();
+        let matches = cmd.clone().get_matches_from(&args);
+        let config_files =
+            ConfigFile::read_all(matches.get_one::<PathBuf>("config").map(PathBuf::as_path))?;
+        Self::insert_config_args(&cmd, &config_files, &mut args, 1, None)?;
+
         let mut opt = Self::parse_from(args);
-        let use_full_default_height = opt.use_full_default_height;
 
         // Parse the modeline if requested
         if opt.modeline {
-            use clap::{CommandFactory, FromArgMatches};
-            let mut cmd = Self::command_for_update().no_binary_name(true);
-            cmd.build();
+            let defaults = ModelineDefaults {
+                config_files,
+                preset: opt.preset.clone(),
+                use_full_default_height: opt.use_full_default_height,
+            };
+
+            let mut cmd = Self::modeline_command();
 
+            // Comments can come before the modeline
+            let format = opt.format();
             let mut first_line = String::new();
-            stdin.read_line(&mut first_line)?;
+            let mut comments = 0;
+            while stdin.read_line(&mut first_line)? > 0 && format.is_comment(&first_line) {
+                first_line.clear();
+                comments += 1;
+            }
 
             if let Some(args) = Self::parse_modeline(&mut cmd, first_line.trim())? {
+                let mut args = args.into_iter().map(OsString::from).collect();
+                defaults.insert_config_args(&cmd, &mut args)?;
                 let matches = cmd.get_matches_from(args);
 
                 opt = Self::from_arg_matches(&matches)?;
@@ -481,27 +1188,179 @@ impl Opt {
                 opt.first_line = Some(FirstLine::Value(first_line));
             }
 
-            opt.use_full_default_height = use_full_default_height;
+            opt.use_full_default_height = defaults.use_full_default_height;
+            opt.modeline_defaults = Some(defaults);
+            opt.line_offset = comments;
         } else {
             opt.first_line = None;
         }
 
-        match (opt.kind(), opt.per) {
-            (GraphKind::Bars | GraphKind::Columns, x) if x > 1 => {
+        opt.finish()
+    }
+
+    /// Parse the options for a later section of the input from its modeline
+    ///
+    /// The modeline is parsed the same way as the first one, with the same config files and
+    /// preset.
+    pub fn try_from_modeline(defaults: ModelineDefaults, line: &str) -> anyhow::Result<Self> {
+        use clap::FromArgMatches;
+
+        let mut cmd = Self::modeline_command();
+        let mut args: Vec<OsString> = Self::parse_modeline(&mut cmd, line.trim())?
+            .unwrap_or_default()
+            .into_iter()
+            .map(OsString::from)
+            .collect();
+
+        // Check the modeline on its own first, so mistakes are returned instead of exiting
+        cmd.clone().try_get_matches_from(&args)?;
+        defaults.insert_config_args(&cmd, &mut args)?;
+        let matches = cmd.try_get_matches_from(args)?;
+
+        let mut opt = Self::from_arg_matches(&matches)?;
+        opt.first_line = Some(FirstLine::ModeLine);
+        opt.use_full_default_height = defaults.use_full_default_height;
+        opt.modeline_defaults = Some(defaults);
+
+        opt.finish()
+    }
+
+    /// Check the options that can't be checked by [`clap`], and find the size of the graph if it
+    /// wasn't given
+    fn finish(mut self) -> anyhow::Result<Self> {
+        if self.file.len() > 1 {
+            if self.time || self.grid.is_some() || self.header {
+                anyhow::bail!(
+                    "Reading more than one file isn't supported with --time, --grid, or --header"
+                );
+            }
+
+            let Ok(files) = u8::try_from(self.file.len()) else {
+                anyhow::bail!("Too many files to read as separate series");
+            };
+            match self.per {
+                1 => self.per = files,
+                per if per != files => {
+                    anyhow::bail!(
+                        "Each file is its own series, but --per is {per} instead of {files}"
+                    );
+                }
+                _ => {}
+            }
+        }
+
+        if self.group && !matches!(self.kind().orientation(), Orientation::Horizontal) {
+            anyhow::bail!("Grouping is only supported for bar graph kinds");
+        }
+
+        match (self.kind(), self.per) {
+            (GraphKind::Bars | GraphKind::Columns, x)
+                if x > 1 && !self.group && !self.stack && self.tile.is_none() =>
+            {
                 anyhow::bail!("Multiple values per line not supported for this graph kind");
             }
             _ => {}
         }
 
+        // Two values on a line are drawn as the span between them, which has no baseline
+        if self.baseline.is_some()
+            && self.per == 2
+            && !self.group
+            && self.tile.is_none()
+            && self.grid.is_none()
+        {
+            anyhow::bail!("--baseline isn't supported with two values per line");
+        }
+
+        if self.time {
+            if !matches!(self.kind().orientation(), Orientation::Vertical) {
+                anyhow::bail!("Time series are only supported for column graph kinds");
+            }
+
+            if self.per != 1 {
+                anyhow::bail!("Time series only support one value per line");
+            }
+        }
+
+        if self.tile.is_some() && !matches!(self.kind().orientation(), Orientation::Vertical) {
+            anyhow::bail!("Tiling is only supported for column graph kinds");
+        }
+
+        if self.annotate
+            && self.grid.is_none()
+            && !matches!(self.kind().orientation(), Orientation::Vertical)
+        {
+            anyhow::bail!("Annotations are only supported for column graph kinds and grids");
+        }
+
+        // Each series is drawn on its own scale, so their values can't be compared to label them
+        if self.annotate && self.independent_scale {
+            anyhow::bail!("Annotations aren't supported with --independent-scale");
+        }
+
+        if self.interactive {
+            if !matches!(self.kind().orientation(), Orientation::Vertical) {
+                anyhow::bail!("Interactive mode is only supported for column graph kinds");
+            }
+
+            if self.per != 1 {
+                anyhow::bail!("Interactive mode only supports one value per line");
+            }
+        }
+
+        if let Some(pattern) = self.pattern.clone().map(Pattern::new) {
+            let expected = if self.parametric.is_some() {
+                1
+            } else if self.grid.is_some() {
+                2
+            } else {
+                usize::from(self.per) + usize::from(self.time)
+            };
+
+            if pattern.len() != expected {
+                anyhow::bail!(
+                    "The pattern picks out {} values per line instead of {expected}",
+                    pattern.len()
+                );
+            }
+
+            if self.legend.is_empty() && !self.header {
+                self.legend = pattern.names().map(String::from).collect();
+            }
+        }
+
+        if self.non_finite == NonFinite::Clip
+            && (self.stack || self.time || self.tile.is_some() || self.grid.is_some())
+        {
+            anyhow::bail!(
+                "Clipping non-finite values isn't supported for stacked, tiled, or time series graphs, or grids"
+            );
+        }
+
+        if !self.hline.is_empty() || !self.vline.is_empty() {
+            match (self.kind(), self.kind().orientation()) {
+                (GraphKind::Bars | GraphKind::Columns, _) => {
+                    anyhow::bail!("Reference lines are not supported for this graph kind");
+                }
+                (_, Orientation::Horizontal) if !self.hline.is_empty() => {
+                    anyhow::bail!("Use --vline for reference lines on bar graphs");
+                }
+                (_, Orientation::Vertical) if !self.vline.is_empty() => {
+                    anyhow::bail!("Use --hline for reference lines on column graphs");
+                }
+                _ => {}
+            }
+        }
+
         // If the graph size isn't already set, try detecting it from the environment
-        if opt.size.is_none() {
+        if self.size.is_none() {
             let (width, height) = util::get_terminal_size()?;
 
-            let size = match opt.kind().orientation() {
+            let size = match self.kind().orientation() {
                 Orientation::Horizontal => width,
                 // Leave enough room for the shell prompt
                 Orientation::Vertical => {
-                    if opt.use_full_default_height {
+                    if self.use_full_default_height {
                         height
                     } else {
                         height - 1
@@ -509,10 +1368,45 @@ impl Opt {
                 }
             };
 
-            opt.size = Some(size);
+            self.size = Some(size);
         }
 
-        Ok(opt)
+        Ok(self)
+    }
+
+    fn modeline_command() -> Command {
+        use clap::CommandFactory;
+
+        let mut cmd = Self::command_for_update().no_binary_name(true);
+        cmd.build();
+        cmd
+    }
+
+    /// Whether a line of input is a modeline, starting a new section with its own options
+    #[must_use]
+    pub fn is_modeline(line: &str) -> bool {
+        line.starts_with("braille")
+    }
+
+    /// Insert the options from config files at `index`, leaving out any given by `args`
+    ///
+    /// The preset given by `args` is used, or `default_preset` if there isn't one.
+    fn insert_config_args(
+        cmd: &Command,
+        config_files: &[ConfigFile],
+        args: &mut Vec<OsString>,
+        index: usize,
+        default_preset: Option<&str>,
+    ) -> anyhow::Result<()> {
+        let matches = cmd.clone().get_matches_from(args.iter());
+        let preset = matches
+            .get_one::<String>("preset")
+            .map(String::as_str)
+            .or(default_preset);
+        let config_args = config_file::to_args(cmd, config_files, preset, &matches)?;
+        args.splice(index..index, config_args);
+
+        Ok(())
     }
 
     /// Parse the first line as a modeline, or try parsing it as the first value
@@ -520,9 +1414,7 @@ impl Opt {
         cmd: &mut Command,
         line: &'a str,
     ) -> Result<Option<Vec<&'a str>>, clap::Error> {
-        if line.starts_with('#') {
-            Ok(Some(vec![]))
-        } else if line.starts_with("braille") {
+        if Self::is_modeline(line) {
             let modeline = line
                 .split_once('#')
                 .map_or(line, |(s, _)| s)
@@ -568,12 +1460,32 @@ The first line should be the string "braille", followed by spaced separated opti
 
     /// If no bounds were given, look for them from the input and return the resulting iterator,
     /// otherwise simply return the resulting iterator.
-    pub fn get_iter<T>(&mut self, input_lines: InputLines<T>) -> anyhow::Result<ValueIter<T>>
+    ///
+    /// Looking for the bounds means reading every line first. Input that can be read again with
+    /// `rewind` only keeps what's needed to find the bounds, and is then read again to draw it,
+    /// instead of keeping every line. Annotated graphs keep every line either way.
+    pub fn get_iter<T: 'static>(
+        &mut self,
+        input_lines: InputLines<T>,
+        rewind: Option<Rewind<T>>,
+    ) -> anyhow::Result<ValueIter<T>>
     where
         InputLine<T>: FromStr + for<'a> InputLineSinglable<'a>,
         <InputLine<T> as FromStr>::Err: std::error::Error + Send + Sync + 'static,
     {
-        if self.range.min().and(self.range.max()).is_some() {
+        let needs_values = self
+            .baseline
+            .is_some_and(|baseline| baseline.values(true).is_some());
+        let needs_stats = self.has_legend();
+        let needs_scales = self.independent_scale;
+
+        // Stacked totals are checked against the range, so they're read even when it's given
+        if self.range.min().and(self.range.max()).is_some()
+            && !needs_values
+            && !needs_stats
+            && !needs_scales
+            && !self.stack
+        {
             match self.kind() {
                 GraphKind::Bars
                 | GraphKind::BrailleBars
@@ -588,35 +1500,331 @@ The first line should be the string "braille", followed by spaced separated opti
                     lines: input_lines.into_iter().collect(),
                 }),
             }
+            .map(|values| self.clip(values))
         } else {
+            // Annotations are placed by looking back over every value, so they keep the lines
+            let rewind = rewind.filter(|_| !self.annotate);
             let mut lines = vec![];
             let has_min = self.range.min().is_some();
             let has_max = self.range.max().is_some();
-            assert!(!has_min || !has_max);
             let mut min = self.range.min().unwrap_or(f64::MAX);
             let mut max = self.range.max().unwrap_or(f64::MIN);
 
-            for line in input_lines {
+            // Each series has its own range when they're scaled independently, once it has a value
+            let fit = move |range: Option<(f64, f64)>, value: f64| {
+                if !value.is_finite() {
+                    return range;
+                }
+
+                let (min, max) = range.unwrap_or((min, max));
+                Some((
+                    if has_min { min } else { min.min(value) },
+                    if has_max { max } else { max.max(value) },
+                ))
+            };
+            let mut series_ranges = vec![None; usize::from(self.per)];
+
+            // Infinite values are clipped to the range, so they don't change it
+            let mut update = |value: f64| {
+                if !value.is_finite() {
+                    return;
+                }
+
+                if !has_min {
+                    min = min.min(value);
+                }
+
+                if !has_max {
+                    max = max.max(value);
+                }
+            };
+
+            // Make sure the reference lines fit in the graph
+            for reference in self.hline.iter().chain(&self.vline) {
+                update(reference.value);
+            }
+
+            let per = usize::from(self.per);
+            if needs_stats {
+                self.series_stats = vec![None; per];
+            }
+
+            let mut values = self
+                .baseline
+                .and_then(|baseline| baseline.values(rewind.is_none()));
+            let given_range = self.range;
+            let mut totals: Option<(f64, f64)> = None;
+            let mut line_count = 0;
+            for (line_number, line) in input_lines.enumerate() {
                 let line = line?;
-                for value in line.as_single_iter().flatten() {
-                    if !has_min {
-                        min = min.min(*value);
+                line_count += 1;
+                if let Some(values) = &mut values {
+                    for value in line.as_single_iter().flatten() {
+                        if value.is_finite() {
+                            values.push(*value);
+                        }
+                    }
+                }
+
+                if needs_stats {
+                    for (index, value) in line.as_single_iter().enumerate() {
+                        let series = if self.group {
+                            // Every group after the first starts with an empty line
+                            match line_number.checked_sub(per) {
+                                None => Some(line_number),
+                                Some(offset) => (offset % (per + 1)).checked_sub(1),
+                            }
+                        } else {
+                            Some(index)
+                        };
+
+                        if let (Some(series), Some(value)) =
+                            (series, value.filter(|value| value.is_finite()))
+                        {
+                            match &mut self.series_stats[series] {
+                                Some(stats) => stats.update(value),
+                                stats @ None => *stats = Some(SeriesStats::new(value)),
+                            }
+                        }
                     }
+                }
 
-                    if !has_max {
-                        max = max.max(*value);
+                if self.stack {
+                    // Stacked series start from zero and end at the running total
+                    let mut total = 0.;
+                    update(total);
+                    for value in line.as_single_iter().flatten() {
+                        total += value;
+                        update(total);
+                        if total.is_finite() {
+                            let (low, high) = totals.unwrap_or((0., 0.));
+                            totals = Some((low.min(total), high.max(total)));
+                        }
+                    }
+                } else {
+                    for value in line.as_single_iter().flatten() {
+                        update(*value);
                     }
                 }
 
-                lines.push(Ok(line));
+                if needs_scales {
+                    for (index, value) in line.as_single_iter().enumerate() {
+                        if index == series_ranges.len() {
+                            series_ranges.push(None);
+                        }
+
+                        if let Some(value) = value {
+                            series_ranges[index] = fit(series_ranges[index], *value);
+                        }
+                    }
+                }
+
+                if rewind.is_none() {
+                    lines.push(Ok(line));
+                }
+            }
+
+            if let Some((low, high)) = totals
+                && (given_range.min().is_some_and(|min| low < min)
+                    || given_range.max().is_some_and(|max| high > max))
+            {
+                anyhow::bail!(
+                    "The stacked totals go from {low} to {high}, which doesn't fit in the range \
+                     {given_range}"
+                );
+            }
+
+            // Lines added since the input was read aren't in the range, so they're left out
+            let rewound = rewind.is_some();
+            let mut values_iter = match rewind {
+                Some(rewind) => ValueIter::Boundless(rewind()?.take_lines(line_count)),
+                None => ValueIter::Bounded { lines },
+            };
+
+            if needs_scales {
+                // Reference lines are drawn on the scale of the first series
+                for reference in self.hline.iter().chain(&self.vline) {
+                    series_ranges[0] = fit(series_ranges[0], reference.value);
+                }
+
+                for (series_min, series_max) in series_ranges.iter().flatten() {
+                    Self::validate_bounds(*series_min, *series_max)?;
+                }
+
+                let to_graph = move |series: usize, value: f64| {
+                    if !value.is_finite() {
+                        return value;
+                    }
+
+                    // Input that changed since it was read may have values without a range
+                    let Some((min, max)) = series_ranges.get(series).copied().flatten() else {
+                        return value;
+                    };
+                    util::scale(value.clamp(min, max), min, max, 0., 1.)
+                };
+
+                for reference in self.hline.iter_mut().chain(&mut self.vline) {
+                    reference.value = to_graph(0, reference.value);
+                }
+
+                values_iter = match values_iter {
+                    ValueIter::Boundless(lines) => {
+                        ValueIter::Boundless(lines.map_lines(move |mut line| {
+                            line.map_values(&to_graph);
+                            line
+                        }))
+                    }
+                    ValueIter::Bounded { mut lines } => {
+                        for line in lines.iter_mut().flatten() {
+                            line.map_values(&to_graph);
+                        }
+                        ValueIter::Bounded { lines }
+                    }
+                };
+
+                min = 0.;
+                max = 1.;
             }
 
             self.range = GraphRange::try_new(Some(min), Some(max))?;
+            if rewound && let ValueIter::Boundless(lines) = values_iter {
+                // Input that changed since it was read may have values outside the range
+                values_iter = ValueIter::Boundless(lines.map_lines(move |mut line| {
+                    line.map_values(|_, value| {
+                        if value.is_finite() {
+                            value.clamp(min, max)
+                        } else {
+                            value
+                        }
+                    });
+                    line
+                }));
+            }
+            if let Some(values) = values {
+                self.baseline = Some(values.resolve());
+            }
+
+            Ok(self.clip(values_iter))
+        }
+    }
+
+    /// Move infinite values to the edge of the graph's range, if they're being clipped
+    fn clip<T: 'static>(&self, values: ValueIter<T>) -> ValueIter<T>
+    where
+        InputLine<T>: FromStr + for<'a> InputLineSinglable<'a>,
+    {
+        let (Some(min), Some(max)) = (self.range.min(), self.range.max()) else {
+            return values;
+        };
+
+        if self.non_finite != NonFinite::Clip {
+            return values;
+        }
+
+        let clip = move |mut line: InputLine<T>| {
+            line.map_values(|_, value| {
+                if value.is_infinite() {
+                    value.clamp(min, max)
+                } else {
+                    value
+                }
+            });
+            line
+        };
+
+        match values {
+            ValueIter::Boundless(lines) => ValueIter::Boundless(lines.map_lines(clip)),
+            ValueIter::Bounded { lines } => ValueIter::Bounded {
+                lines: lines.into_iter().map(|line| line.map(clip)).collect(),
+            },
+        }
+    }
+
+    /// Find the range of each tiled series, using the bounds given where there are any
+    ///
+    /// The range of the whole graph is set to fit every series (and reference line), which is
+    /// what every tile uses unless they each have their own range.
+    pub fn tile_ranges(&mut self, series: &[Vec<Option<f64>>]) -> anyhow::Result<Vec<(f64, f64)>> {
+        let range = self.range;
+        let fit = |values: &[f64]| -> anyhow::Result<(f64, f64)> {
+            let min = range
+                .min()
+                .unwrap_or_else(|| values.iter().copied().fold(f64::MAX, f64::min));
+            let max = range
+                .max()
+                .unwrap_or_else(|| values.iter().copied().fold(f64::MIN, f64::max));
+            Self::validate_bounds(min, max)?;
+            Ok((min, max))
+        };
+
+        let references: Vec<f64> = self.hline.iter().map(|reference| reference.value).collect();
+        let all: Vec<f64> = series
+            .iter()
+            .flatten()
+            .flatten()
+            .copied()
+            .chain(references.iter().copied())
+            .collect();
+
+        let (min, max) = fit(&all)?;
+        self.range = GraphRange::try_new(Some(min), Some(max))?;
+
+        match self.tile_range {
+            TileRange::Shared => Ok(vec![(min, max); series.len()]),
+            TileRange::Independent => series
+                .iter()
+                .map(|values| {
+                    let values: Vec<f64> = values.iter().flatten().copied().collect();
+                    if values.is_empty() {
+                        // There's nothing to fit, so use the range of everything else
+                        Ok((min, max))
+                    } else {
+                        fit(&[values, references.clone()].concat())
+                    }
+                })
+                .collect(),
+        }
+    }
 
-            Ok(ValueIter::Bounded { lines })
+    /// The range of the graph, which is only what was given until it's determined from the input
+    #[must_use]
+    pub fn range(&self) -> GraphRange {
+        self.range
+    }
+
+    /// How values are written in the input
+    #[must_use]
+    pub fn format(&self) -> Format {
+        Format {
+            null_values: self.null_values.clone(),
+            non_finite: self.non_finite,
+            comment_prefix: self.comment_prefix.clone(),
+            unit: self.units.then(|| Rc::clone(&self.unit)),
+            pattern: self.pattern.clone().map(Pattern::new),
+            unmatched: self.unmatched,
+        }
+    }
+
+    /// How lines of the input are read, after the modeline if there was one
+    #[must_use]
+    pub fn read_options(&self) -> ReadOptions {
+        ReadOptions {
+            line_number: self.line_offset
+                + 1
+                + usize::from(matches!(self.first_line, Some(FirstLine::ModeLine))),
+            lossy: self.lossy,
+            on_error: self.on_error,
+            skipped: Rc::clone(&self.skipped_lines),
+            format: self.format(),
         }
     }
 
+    /// Whether a legend should be printed below the graph
+    #[must_use]
+    pub fn has_legend(&self) -> bool {
+        self.header || !self.legend.is_empty()
+    }
+
     /// Get the kind of graph to use, handling shortcuts
     #[must_use]
     pub fn kind(&self) -> GraphKind {
@@ -644,6 +1852,9 @@ The first line should be the string "braille", followed by spaced separated opti
     }
 }
 
+/// Read the input again from the start
+pub type Rewind<T> = Box<dyn FnOnce() -> anyhow::Result<InputLines<T>>>;
+
 pub enum ValueIter<T>
 where
     InputLine<T>: FromStr,
@@ -781,6 +1992,28 @@ pub enum CharType {
     Octant,
 }
 
+impl CharType {
+    /// How many values are drawn side by side in each character of a column graph
+    #[must_use]
+    pub fn values_per_column(self) -> u16 {
+        match self {
+            Self::Block | Self::Column => 1,
+            Self::Braille | Self::HalfBlock | Self::Sextant | Self::Octant => 2,
+        }
+    }
+
+    /// How many steps each character of a column graph is divided into from bottom to top
+    #[must_use]
+    pub fn steps_per_row(self) -> u16 {
+        match self {
+            Self::Block | Self::Column => 8,
+            Self::Braille | Self::Octant => 4,
+            Self::Sextant => 3,
+            Self::HalfBlock => 2,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -852,4 +2085,171 @@ mod tests {
         terr!(parse_invalid_lower, "hello:");
         terr!(parse_backwards, "3:2");
     }
+
+    mod baseline {
+        use super::*;
+
+        #[test]
+        fn parse_baseline() {
+            assert_eq!(Baseline::Value(-3.5), "-3.5".parse().unwrap());
+            assert_eq!(Baseline::Mean, "mean".parse().unwrap());
+            assert_eq!(Baseline::Median, "median".parse().unwrap());
+            assert_eq!(Baseline::Min, "min".parse().unwrap());
+            assert!("max".parse::<Baseline>().is_err());
+        }
+
+        fn resolve(baseline: Baseline, every_value: bool, values: &[f64]) -> Baseline {
+            let mut kept = baseline.values(every_value).unwrap();
+            for value in values {
+                kept.push(*value);
+            }
+
+            kept.resolve()
+        }
+
+        #[test]
+        fn resolve_mean() {
+            assert_eq!(
+                Baseline::Value(3.),
+                resolve(Baseline::Mean, true, &[1., 2., 6.])
+            );
+            assert_eq!(
+                Baseline::Value(3.),
+                resolve(Baseline::Mean, false, &[1., 2., 6.])
+            );
+            assert_eq!(Baseline::Value(0.), resolve(Baseline::Mean, false, &[]));
+        }
+
+        #[test]
+        fn resolve_median() {
+            for every_value in [true, false] {
+                let median = |values| resolve(Baseline::Median, every_value, values);
+                assert_eq!(Baseline::Value(2.), median(&[6., 1., 2.]));
+                assert_eq!(Baseline::Value(2.5), median(&[6., 1., 2., 3.]));
+                assert_eq!(Baseline::Value(0.), median(&[]));
+            }
+        }
+
+        #[test]
+        fn values_kept() {
+            assert!(Baseline::Value(1.).values(true).is_none());
+            assert!(Baseline::Min.values(true).is_none());
+            assert!(matches!(
+                Baseline::Median.values(false),
+                Some(BaselineValues::Sketch(_))
+            ));
+        }
+    }
+
+    mod get_iter {
+        use super::*;
+        use std::io::Cursor;
+
+        fn lines(input: &'static str) -> InputLines<Option<f64>> {
+            InputLines::from_buf_reader(None, Cursor::new(input), ReadOptions::default())
+        }
+
+        /// The options and values drawn when `first` is read to find the range, and `second` to
+        /// draw it
+        fn rewound_with(
+            args: &[&str],
+            first: &'static str,
+            second: &'static str,
+        ) -> (Opt, Vec<Option<f64>>) {
+            let args = ["braille", "4"].iter().chain(args);
+            // Without reading any config files
+            let mut opt = Opt::parse_from(args).finish().unwrap();
+            let values = opt
+                .get_iter(lines(first), Some(Box::new(move || Ok(lines(second)))))
+                .unwrap()
+                .into_iter()
+                .map(|line| line.unwrap().into_inner())
+                .collect();
+            (opt, values)
+        }
+
+        fn rewound(first: &'static str, second: &'static str) -> (GraphRange, Vec<Option<f64>>) {
+            let (opt, values) = rewound_with(&[], first, second);
+            (opt.range, values)
+        }
+
+        #[test]
+        fn rewind() {
+            let (range, values) = rewound("1\n3\n2\n", "1\n3\n2\n");
+            assert_eq!((Some(1.), Some(3.)), (range.min(), range.max()));
+            assert_eq!(vec![Some(1.), Some(3.), Some(2.)], values);
+        }
+
+        #[test]
+        fn rewind_with_baseline() {
+            for (baseline, expected) in [("mean", 4.), ("median", 3.)] {
+                let input = "1\n8\n3\n";
+                let (opt, values) = rewound_with(&["--baseline", baseline], input, input);
+                assert_eq!(Some(Baseline::Value(expected)), opt.baseline, "{baseline}");
+                assert_eq!(vec![Some(1.), Some(8.), Some(3.)], values);
+            }
+        }
+
+        #[test]
+        fn rewind_columns() {
+            let (opt, values) = rewound_with(&["-c"], "1\n3\n2\n", "1\n3\n2\n");
+            assert_eq!((Some(1.), Some(3.)), (opt.range.min(), opt.range.max()));
+            assert_eq!(vec![Some(1.), Some(3.), Some(2.)], values);
+        }
+
+        #[test]
+        fn annotations_keep_lines() {
+            let mut opt = Opt::parse_from(["braille", "-c", "--annotate", "4"])
+                .finish()
+                .unwrap();
+            let values = opt
+                .get_iter(
+                    lines("1\n3\n"),
+                    Some(Box::new(|| {
+                        panic!("annotated input shouldn't be read again")
+                    })),
+                )
+                .unwrap();
+            assert!(matches!(values, ValueIter::Bounded { .. }));
+        }
+
+        #[test]
+        fn rewind_leaves_out_added_lines() {
+            let (_, values) = rewound("1\n3\n", "1\n3\n9\n");
+            assert_eq!(vec![Some(1.), Some(3.)], values);
+        }
+
+        #[test]
+        fn rewind_clamps_changed_values() {
+            let (_, values) = rewound("1\n3\n", "-5\n30\n");
+            assert_eq!(vec![Some(1.), Some(3.)], values);
+        }
+    }
+
+    mod reference_line {
+        use super::*;
+
+        #[test]
+        fn parse_value_only() {
+            let expected = ReferenceLine {
+                value: -2.5,
+                label: None,
+            };
+            assert_eq!(expected, "-2.5".parse().unwrap());
+        }
+
+        #[test]
+        fn parse_with_label() {
+            let expected = ReferenceLine {
+                value: 250.,
+                label: Some(String::from("p95: SLO")),
+            };
+            assert_eq!(expected, "250:p95: SLO".parse().unwrap());
+        }
+
+        #[test]
+        fn parse_invalid() {
+            assert!("SLO:250".parse::<ReferenceLine>().is_err());
+        }
+    }
 }
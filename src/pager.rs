@@ -0,0 +1,480 @@
+//! A full screen view of a column graph, for series too long to fit in the terminal.
+//!
+//! The whole series is kept in memory, and only the part of it in view is drawn. Zooming out
+//! combines neighbouring values (like `--tile`), and the range is fit to what's in view.
+
+use std::io::{LineWriter, prelude::*};
+
+use crossterm::cursor::{Hide, MoveTo, MoveToNextLine, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::graph::Graphable;
+use crate::input::Line as InputLine;
+use crate::opt::{Aggregate, Config, Configurable, GraphRange, Opt, ValueIter};
+use crate::units::{self, Unit};
+use crate::{annotate, legend};
+
+/// View the graph of the input full screen until the user quits
+pub fn run<Graph, W>(
+    mut opt: Opt,
+    input: Box<dyn BufRead>,
+    mut writer: LineWriter<W>,
+) -> anyhow::Result<()>
+where
+    Graph: Graphable<Option<f64>>,
+    W: Write,
+{
+    let given = opt.range();
+    let lines = crate::read_lines::<Option<f64>>(&mut opt, input)?;
+    let ValueIter::Bounded { lines } = opt.get_iter(lines, None)? else {
+        unreachable!("Column graphs should always be bounded");
+    };
+    let values = lines
+        .into_iter()
+        .map(|line| line.map(InputLine::into_inner))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let references: Vec<f64> = opt.hline.iter().map(|reference| reference.value).collect();
+    let char_type = opt.kind().char_type();
+    let values_per_column = usize::from(char_type.values_per_column());
+    let aggregate = opt.aggregate;
+    let mut annotate = opt.annotate;
+    let title = opt.title.take();
+    let unit = opt.unit.get();
+    let config = Config::from(opt);
+
+    let mut screen = Screen::enter(&mut writer)?;
+    let mut view = View::default();
+    loop {
+        let (width, height) = terminal::size()?;
+        let width = usize::from(width);
+        let count = width * values_per_column;
+        view.fit(values.len(), count);
+
+        // Leave room for the title and the status line
+        let graph_height = height.saturating_sub(1 + u16::from(title.is_some())).max(1);
+
+        let plotted = view.plotted(&values, count, aggregate);
+        let (min, max) = fit_range(given, &references, plotted.iter().flatten().copied())
+            .unwrap_or((config.minimum(), config.maximum()));
+
+        let mut buffer = vec![];
+        Graph::from(config.resized(min, max, graph_height)).print_graph(
+            ValueIter::Bounded {
+                lines: plotted
+                    .iter()
+                    .map(|value| Ok(InputLine::new(*value)))
+                    .collect(),
+            },
+            LineWriter::new(&mut buffer),
+        )?;
+        let mut graph = String::from_utf8(buffer)?;
+
+        if annotate {
+            let lines: Vec<&str> = graph.lines().collect();
+            let points: Vec<(usize, f64)> = plotted
+                .iter()
+                .enumerate()
+                .filter_map(|(index, value)| value.map(|value| (index, value)))
+                .collect();
+            let marks = annotate::column_marks(
+                &lines,
+                &points,
+                values_per_column,
+                usize::from(char_type.steps_per_row()),
+                (min, max),
+                unit,
+            );
+            graph = annotate::label(&lines, &marks, width).join("\n");
+        }
+
+        let status = view.status(values.len(), count, plotted.get(view.cursor).copied(), unit);
+        screen.draw(
+            title.as_deref(),
+            &graph,
+            view.cursor / values_per_column,
+            &status,
+            width,
+        )?;
+
+        let Event::Key(key) = event::read()? else {
+            // Anything else (like the terminal being resized) just redraws the graph
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            KeyCode::Left | KeyCode::Char('h') => view.move_cursor(-1, values.len(), count),
+            KeyCode::Right | KeyCode::Char('l') => view.move_cursor(1, values.len(), count),
+            KeyCode::PageUp | KeyCode::Char('H') => view.scroll(-1, values.len(), count),
+            KeyCode::PageDown | KeyCode::Char('L') => view.scroll(1, values.len(), count),
+            KeyCode::Home | KeyCode::Char('g') => {
+                view = View {
+                    zoom: view.zoom,
+                    ..View::default()
+                }
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                view.scroll_to(values.len().saturating_sub(1), count);
+            }
+            KeyCode::Char('a') => annotate = !annotate,
+            KeyCode::Char('+' | '=') => view.zoom_in(values.len(), count),
+            KeyCode::Char('-' | '_') => view.zoom_out(values.len(), count),
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The terminal in raw mode on the alternate screen, which is put back the way it was when
+/// dropped (even if drawing fails)
+struct Screen<'a, W: Write> {
+    writer: &'a mut W,
+}
+
+impl<'a, W: Write> Screen<'a, W> {
+    fn enter(writer: &'a mut W) -> std::io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Self { writer };
+        execute!(screen.writer, EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+
+    /// Draw the graph with the cursor's column highlighted, and the status line below it
+    fn draw(
+        &mut self,
+        title: Option<&str>,
+        graph: &str,
+        cursor: usize,
+        status: &str,
+        width: usize,
+    ) -> std::io::Result<()> {
+        queue!(self.writer, MoveTo(0, 0), Clear(ClearType::All))?;
+
+        if let Some(title) = title {
+            queue!(
+                self.writer,
+                Print(legend::truncate(title, width)),
+                MoveToNextLine(1)
+            )?;
+        }
+
+        for line in graph.lines() {
+            let mut line: Vec<char> = line.chars().take(width).collect();
+            if line.len() <= cursor {
+                line.resize(cursor + 1, ' ');
+            }
+
+            let before: String = line[..cursor].iter().collect();
+            let after: String = line[cursor + 1..].iter().collect();
+            queue!(
+                self.writer,
+                Print(before),
+                SetAttribute(Attribute::Reverse),
+                Print(line[cursor]),
+                SetAttribute(Attribute::Reset),
+                Print(after),
+                MoveToNextLine(1)
+            )?;
+        }
+
+        queue!(self.writer, Print(legend::truncate(status, width)))?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for Screen<'_, W> {
+    fn drop(&mut self) {
+        // There's nothing left to do if this fails, so don't hide the error that got us here
+        let _ = execute!(self.writer, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// The part of the series in view, where `count` values are plotted across the terminal
+///
+/// Each plotted value combines `zoom` values of the series, starting from `start`. The cursor
+/// counts plotted values from the left edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct View {
+    start: usize,
+    zoom: usize,
+    cursor: usize,
+}
+
+impl Default for View {
+    fn default() -> Self {
+        Self {
+            start: 0,
+            zoom: 1,
+            cursor: 0,
+        }
+    }
+}
+
+impl View {
+    /// How many values of the series are in view
+    fn span(self, count: usize) -> usize {
+        count * self.zoom
+    }
+
+    /// Where the cursor is in the series
+    fn position(self) -> usize {
+        self.start + self.cursor * self.zoom
+    }
+
+    /// Keep the view within the `len` values of the series, like after the terminal is resized
+    fn fit(&mut self, len: usize, count: usize) {
+        self.start = self.start.min(len.saturating_sub(self.span(count)));
+
+        let shown = (len - self.start).min(self.span(count)).div_ceil(self.zoom);
+        self.cursor = self.cursor.min(shown.saturating_sub(1));
+    }
+
+    /// Move the cursor to `position` in the series, scrolling just far enough to keep it in view
+    fn scroll_to(&mut self, position: usize, count: usize) {
+        if position < self.start {
+            self.start = position;
+        } else if position >= self.start + self.span(count) {
+            self.start = position + self.zoom - self.span(count);
+        }
+
+        self.cursor = (position - self.start) / self.zoom;
+    }
+
+    /// Move the cursor `by` plotted values left or right
+    fn move_cursor(&mut self, by: isize, len: usize, count: usize) {
+        let step = by.saturating_mul(isize::try_from(self.zoom).unwrap_or(isize::MAX));
+        let position = self
+            .position()
+            .saturating_add_signed(step)
+            .min(len.saturating_sub(1));
+        self.scroll_to(position, count);
+        self.fit(len, count);
+    }
+
+    /// Scroll `by` screens left or right, leaving the cursor where it is on screen
+    fn scroll(&mut self, by: isize, len: usize, count: usize) {
+        let step = by.saturating_mul(isize::try_from(self.span(count)).unwrap_or(isize::MAX));
+        self.start = self.start.saturating_add_signed(step);
+        self.fit(len, count);
+    }
+
+    /// Combine half as many values into each plotted value, keeping the cursor where it is
+    fn zoom_in(&mut self, len: usize, count: usize) {
+        if self.zoom > 1 {
+            self.rezoom(self.zoom / 2, len, count);
+        }
+    }
+
+    /// Combine twice as many values into each plotted value, until the whole series is in view
+    fn zoom_out(&mut self, len: usize, count: usize) {
+        if self.span(count) < len {
+            self.rezoom(self.zoom * 2, len, count);
+        }
+    }
+
+    fn rezoom(&mut self, zoom: usize, len: usize, count: usize) {
+        let position = self.position();
+        self.zoom = zoom;
+        self.start = position.saturating_sub(self.cursor * zoom);
+        self.fit(len, count);
+        self.scroll_to(position.max(self.start), count);
+    }
+
+    /// The values in view, with every `zoom` values combined
+    fn plotted(
+        self,
+        values: &[Option<f64>],
+        count: usize,
+        aggregate: Aggregate,
+    ) -> Vec<Option<f64>> {
+        let end = values.len().min(self.start + self.span(count));
+        values[self.start.min(end)..end]
+            .chunks(self.zoom)
+            .map(|chunk| aggregate.combine(chunk.iter().flatten().copied()))
+            .collect()
+    }
+
+    /// Which values are under the cursor and what they're plotted as, and which are in view
+    ///
+    /// Values are numbered from 1, like the lines of the input.
+    fn status(
+        self,
+        len: usize,
+        count: usize,
+        value: Option<Option<f64>>,
+        unit: Option<Unit>,
+    ) -> String {
+        let numbers = |start: usize, end: usize| {
+            if end - start > 1 {
+                format!("{}-{}", start + 1, end)
+            } else {
+                (start + 1).to_string()
+            }
+        };
+
+        let position = self.position();
+        let under_cursor = numbers(position, len.min(position + self.zoom).max(position + 1));
+        let value = match value {
+            Some(Some(value)) => units::label(value, unit),
+            Some(None) => String::from("none"),
+            None => String::from("no values"),
+        };
+        let in_view = numbers(self.start, len.min(self.start + self.span(count)));
+
+        format!(
+            "{under_cursor}: {value} (showing {in_view} of {len}, zoom {}x)",
+            self.zoom
+        )
+    }
+}
+
+/// Fit the range to the values in view and the reference lines, keeping any bounds given
+///
+/// There's nothing to fit if there aren't any values in view.
+fn fit_range(
+    given: GraphRange,
+    references: &[f64],
+    values: impl IntoIterator<Item = f64>,
+) -> Option<(f64, f64)> {
+    let mut values = values.into_iter().peekable();
+    values.peek()?;
+
+    let (min, max) = values
+        .chain(references.iter().copied())
+        .fold((f64::MAX, f64::MIN), |(min, max), value| {
+            (min.min(value), max.max(value))
+        });
+
+    let min = given.min().unwrap_or(min);
+    let max = given.max().unwrap_or(max);
+
+    // The values in view could all be past the one bound that was given
+    Some((min, max.max(min)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn move_cursor_scrolls() {
+        let mut view = View::default();
+
+        view.move_cursor(-1, 20, 4);
+        assert_eq!(View::default(), view);
+
+        view.move_cursor(5, 20, 4);
+        assert_eq!(
+            View {
+                start: 2,
+                zoom: 1,
+                cursor: 3
+            },
+            view
+        );
+
+        view.move_cursor(-3, 20, 4);
+        view.move_cursor(-1, 20, 4);
+        assert_eq!(
+            View {
+                start: 1,
+                zoom: 1,
+                cursor: 0
+            },
+            view
+        );
+
+        view.move_cursor(100, 20, 4);
+        assert_eq!(
+            View {
+                start: 16,
+                zoom: 1,
+                cursor: 3
+            },
+            view
+        );
+    }
+
+    #[test]
+    fn zoom_keeps_cursor() {
+        let mut view = View {
+            start: 8,
+            zoom: 1,
+            cursor: 2,
+        };
+
+        view.zoom_out(20, 4);
+        assert_eq!(
+            View {
+                start: 6,
+                zoom: 2,
+                cursor: 2
+            },
+            view
+        );
+
+        // The whole series is in view, so it's as far out as it goes
+        view.zoom_out(20, 4);
+        view.zoom_out(20, 4);
+        assert_eq!(
+            View {
+                start: 0,
+                zoom: 8,
+                cursor: 1
+            },
+            view
+        );
+        assert_eq!(8, view.position());
+
+        view.zoom_in(20, 4);
+        assert_eq!(
+            View {
+                start: 4,
+                zoom: 4,
+                cursor: 1
+            },
+            view
+        );
+    }
+
+    #[test]
+    fn plotted_values() {
+        let values = [Some(1.), Some(3.), None, None, Some(5.)];
+        let view = View {
+            start: 1,
+            zoom: 2,
+            cursor: 0,
+        };
+
+        assert_eq!(
+            vec![Some(3.), Some(5.)],
+            view.plotted(&values, 4, Aggregate::Avg)
+        );
+        assert_eq!(
+            "2-3: 3 (showing 2-5 of 5, zoom 2x)",
+            view.status(5, 4, Some(Some(3.)), None)
+        );
+    }
+
+    #[test]
+    fn fit_range_to_view() {
+        let given = GraphRange::default();
+        assert_eq!(Some((1., 4.)), fit_range(given, &[], [3., 1., 4.]));
+        assert_eq!(Some((-1., 4.)), fit_range(given, &[-1.], [3., 1., 4.]));
+        assert_eq!(None, fit_range(given, &[-1.], []));
+
+        let given: GraphRange = "5:".parse().unwrap();
+        assert_eq!(Some((5., 5.)), fit_range(given, &[], [3., 1., 4.]));
+    }
+}
@@ -0,0 +1,174 @@
+//! Estimating a quantile of values without keeping them, with the P² algorithm
+//!
+//! Five markers follow the minimum, the quantile, the maximum, and the points halfway between
+//! them, moving along a parabola through their neighbours as values are added. See Jain and
+//! Chlamtac, "The P² algorithm for dynamic calculation of quantiles and histograms without
+//! storing observations" (1985).
+
+/// A running estimate of one quantile
+#[derive(Debug, Clone)]
+pub struct Quantile {
+    /// Which quantile, from 0 to 1
+    p: f64,
+
+    /// How many values were added
+    count: usize,
+
+    /// The height of each marker, which are the first values until there are five
+    heights: [f64; 5],
+
+    /// The position of each marker, counting from zero
+    positions: [f64; 5],
+
+    /// Where each marker should be
+    desired: [f64; 5],
+
+    /// How far each marker should move for each value
+    increments: [f64; 5],
+}
+
+impl Quantile {
+    #[must_use]
+    pub fn new(p: f64) -> Self {
+        Self {
+            p,
+            count: 0,
+            heights: [0.; 5],
+            positions: [0., 1., 2., 3., 4.],
+            desired: [0., 2. * p, 4. * p, 2. + 2. * p, 4.],
+            increments: [0., p / 2., p, (1. + p) / 2., 1.],
+        }
+    }
+
+    pub fn push(&mut self, value: f64) {
+        if self.count < 5 {
+            self.heights[self.count] = value;
+            self.count += 1;
+            if self.count == 5 {
+                self.heights.sort_by(f64::total_cmp);
+            }
+
+            return;
+        }
+
+        self.count += 1;
+
+        // The cell the value falls in, stretching the ends to fit it
+        let cell = if value < self.heights[0] {
+            self.heights[0] = value;
+            0
+        } else if value >= self.heights[4] {
+            self.heights[4] = value;
+            3
+        } else {
+            (1..5)
+                .find(|&marker| value < self.heights[marker])
+                .map_or(3, |marker| marker - 1)
+        };
+
+        for position in &mut self.positions[cell + 1..] {
+            *position += 1.;
+        }
+
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        for marker in 1..4 {
+            let offset = self.desired[marker] - self.positions[marker];
+            let room_after = self.positions[marker + 1] - self.positions[marker];
+            let room_before = self.positions[marker - 1] - self.positions[marker];
+            if (offset >= 1. && room_after > 1.) || (offset <= -1. && room_before < -1.) {
+                let step = offset.signum();
+                let height = self.parabolic(marker, step);
+                self.heights[marker] =
+                    if self.heights[marker - 1] < height && height < self.heights[marker + 1] {
+                        height
+                    } else {
+                        self.linear(marker, step)
+                    };
+                self.positions[marker] += step;
+            }
+        }
+    }
+
+    /// The quantile of the values added so far, which is exact until there are more than five
+    #[must_use]
+    pub fn estimate(&self) -> Option<f64> {
+        match self.count {
+            0 => None,
+            1..5 => {
+                let mut values = self.heights[..self.count].to_vec();
+                values.sort_by(f64::total_cmp);
+
+                #[allow(clippy::cast_precision_loss)]
+                let position = self.p * (self.count - 1) as f64;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let below = position.floor() as usize;
+                let above = (below + 1).min(self.count - 1);
+                let fraction = position - position.floor();
+                Some(values[below] + (values[above] - values[below]) * fraction)
+            }
+            _ => Some(self.heights[2]),
+        }
+    }
+
+    /// Where a marker moves to along the parabola through it and its neighbours
+    fn parabolic(&self, marker: usize, step: f64) -> f64 {
+        let (q, n) = (&self.heights, &self.positions);
+        q[marker]
+            + step / (n[marker + 1] - n[marker - 1])
+                * ((n[marker] - n[marker - 1] + step) * (q[marker + 1] - q[marker])
+                    / (n[marker + 1] - n[marker])
+                    + (n[marker + 1] - n[marker] - step) * (q[marker] - q[marker - 1])
+                        / (n[marker] - n[marker - 1]))
+    }
+
+    /// Where a marker moves to along the line towards the neighbour it's moving to
+    fn linear(&self, marker: usize, step: f64) -> f64 {
+        let neighbour = if step > 0. { marker + 1 } else { marker - 1 };
+        let (q, n) = (&self.heights, &self.positions);
+        q[marker] + step * (q[neighbour] - q[marker]) / (n[neighbour] - n[marker])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn median(values: impl IntoIterator<Item = f64>) -> Option<f64> {
+        let mut quantile = Quantile::new(0.5);
+        for value in values {
+            quantile.push(value);
+        }
+
+        quantile.estimate()
+    }
+
+    #[test]
+    fn exact_for_few_values() {
+        assert_eq!(None, median([]));
+        assert_eq!(Some(3.), median([3.]));
+        assert_eq!(Some(2.), median([6., 1., 2.]));
+        assert_eq!(Some(2.5), median([6., 1., 2., 3.]));
+    }
+
+    #[test]
+    fn estimates_many_values() {
+        // Every number from 0 to 1000, in a scrambled order
+        let values = (0..=1000).map(|x| f64::from((x * 7919) % 1001));
+        let estimate = median(values).unwrap();
+        assert!((estimate - 500.).abs() < 10., "{estimate}");
+    }
+
+    #[test]
+    fn estimates_other_quantiles() {
+        let mut quantile = Quantile::new(0.9);
+        for x in 0..=1000 {
+            quantile.push(f64::from((x * 7919) % 1001));
+        }
+
+        let estimate = quantile.estimate().unwrap();
+        assert!((estimate - 900.).abs() < 10., "{estimate}");
+    }
+}
@@ -0,0 +1,168 @@
+//! Small multiples: each series drawn as its own column graph, tiled across the terminal.
+//!
+//! # Example
+//!
+//! ```console
+//! $ paste -d, <(seq 1 8) <(seq 8 -1 1) <(seq 4 -1 1; seq 1 4) | braille -C --tile x3 --legend up,down,dip 4
+//! up       down     dip
+//!      ▁▄█ █▄▁
+//!    ▂▆███ ███▆▂    ▂      ▂
+//! ▁▄▇█████ █████▇▄▁ █▇▄▁▁▄▇█
+//! ```
+
+use std::io::{LineWriter, prelude::*};
+
+use crate::graph::Graphable;
+use crate::input::Line as InputLine;
+use crate::opt::{Aggregate, Config, Opt, ValueIter};
+use crate::{legend, util};
+
+/// Print each series as its own graph, tiled in rows and columns
+pub fn print_tiles<Graph, W>(
+    mut opt: Opt,
+    input: Box<dyn BufRead>,
+    mut writer: LineWriter<W>,
+) -> anyhow::Result<()>
+where
+    Graph: Graphable<Option<f64>>,
+    W: Write,
+{
+    let lines = crate::read_lines::<Vec<Option<f64>>>(&mut opt, input)?;
+
+    // Each series is a column of the input, with missing values at the end of short lines
+    let mut series: Vec<Vec<Option<f64>>> = vec![vec![]; usize::from(opt.per)];
+    for (index, line) in lines.enumerate() {
+        for (column, value) in line?.into_iter().enumerate() {
+            if column == series.len() {
+                series.push(vec![None; index]);
+            }
+
+            series[column].push(value);
+        }
+
+        for values in &mut series {
+            values.resize(index + 1, None);
+        }
+    }
+
+    let width = usize::from(util::get_terminal_size()?.0);
+    let size = opt.size.unwrap_or_default();
+    let height = usize::from(size.saturating_sub(u16::from(opt.title.is_some())));
+    let (rows, columns) = opt.tile.unwrap_or_default().layout(series.len())?;
+
+    // Each row of tiles needs a line for the names and at least one for the graphs
+    if height < rows * 2 {
+        anyhow::bail!(
+            "A height of {size} is too small for {rows} row{} of tiles{}",
+            if rows == 1 { "" } else { "s" },
+            if opt.title.is_some() {
+                " and a title"
+            } else {
+                ""
+            }
+        );
+    }
+
+    // Leave a space between each tile, and a line above each one for its name
+    let tile_width = ((width + 1) / columns).saturating_sub(1).max(1);
+    let tile_height = height / rows - 1;
+
+    let count = tile_width * usize::from(opt.kind().char_type().values_per_column());
+    let series: Vec<_> = series
+        .into_iter()
+        .map(|values| downsample(values, count, opt.aggregate))
+        .collect();
+
+    let ranges = opt.tile_ranges(&series)?;
+    let names = std::mem::take(&mut opt.legend);
+    let title = opt.title.take();
+    let config = Config::from(opt);
+
+    let mut tiles = vec![];
+    for (index, (values, (min, max))) in series.into_iter().zip(ranges).enumerate() {
+        let name = names
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| (index + 1).to_string());
+
+        let mut buffer = vec![];
+        let lines = values.into_iter().map(|value| Ok(InputLine::new(value)));
+        Graph::from(config.resized(min, max, u16::try_from(tile_height)?)).print_graph(
+            ValueIter::Bounded {
+                lines: lines.collect(),
+            },
+            LineWriter::new(&mut buffer),
+        )?;
+
+        let graph = String::from_utf8(buffer)?;
+        let tile: Vec<_> = std::iter::once(name.as_str())
+            .chain(graph.lines())
+            .map(|line| pad(line, tile_width))
+            .collect();
+        tiles.push(tile);
+    }
+
+    if let Some(title) = title {
+        writeln!(writer, "{}", legend::truncate(&title, width))?;
+    }
+
+    let blank = " ".repeat(tile_width);
+    for row in tiles.chunks(columns) {
+        let lines = row.iter().map(Vec::len).max().unwrap_or_default();
+        for line in 0..lines {
+            let line = row
+                .iter()
+                .map(|tile| tile.get(line).unwrap_or(&blank).as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            writeln!(writer, "{}", line.trim_end())?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Combine neighbouring values so there are at most `count` of them
+fn downsample(values: Vec<Option<f64>>, count: usize, aggregate: Aggregate) -> Vec<Option<f64>> {
+    if values.len() <= count {
+        return values;
+    }
+
+    values
+        .chunks(values.len().div_ceil(count))
+        .map(|chunk| aggregate.combine(chunk.iter().flatten().copied()))
+        .collect()
+}
+
+/// Cut or pad a line with spaces to be exactly `width` characters wide
+fn pad(line: &str, width: usize) -> String {
+    let line = legend::truncate(line, width);
+    let padding = width - line.chars().count();
+    line + &" ".repeat(padding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn downsample_values() {
+        let values = vec![Some(1.), Some(3.), None, None, Some(5.)];
+
+        assert_eq!(
+            vec![Some(2.), None, Some(5.)],
+            downsample(values.clone(), 3, Aggregate::Avg)
+        );
+        assert_eq!(
+            vec![Some(3.), None, Some(5.)],
+            downsample(values.clone(), 3, Aggregate::Max)
+        );
+        assert_eq!(values, downsample(values.clone(), 5, Aggregate::Avg));
+    }
+
+    #[test]
+    fn pad_lines() {
+        assert_eq!("⣿⣿  ", pad("⣿⣿", 4));
+        assert_eq!("⣿⣿", pad("⣿⣿⣿ label", 2));
+    }
+}
@@ -0,0 +1,442 @@
+//! Timestamps for placing values along the x axis by time.
+//!
+//! Times are kept as seconds since the Unix epoch, and always shown in UTC.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::opt::Aggregate;
+
+const MINUTE: f64 = 60.;
+const HOUR: f64 = 60. * MINUTE;
+const DAY: f64 = 24. * HOUR;
+
+/// Seconds since the Unix epoch
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Timestamp(pub f64);
+
+#[derive(Debug, PartialEq)]
+pub struct TimestampParseError(String);
+
+impl fmt::Display for TimestampParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Failed to parse timestamp {:?}: expected RFC 3339 or seconds since the Unix epoch",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for TimestampParseError {}
+
+impl FromStr for Timestamp {
+    type Err = TimestampParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(seconds) = s.parse::<f64>()
+            && seconds.is_finite()
+        {
+            return Ok(Self(seconds));
+        }
+
+        parse_rfc3339(s)
+            .map(Self)
+            .ok_or_else(|| TimestampParseError(s.to_string()))
+    }
+}
+
+/// Parse an RFC 3339 date (`2024-05-01`) or date and time (`2024-05-01T13:45:00.5+02:00`)
+fn parse_rfc3339(s: &str) -> Option<f64> {
+    let number = |range: std::ops::Range<usize>| -> Option<i64> {
+        let digits = s.get(range)?;
+        digits
+            .bytes()
+            .all(|b| b.is_ascii_digit())
+            .then(|| digits.parse().ok())?
+    };
+    let separator =
+        |index: usize, valid: &[u8]| s.as_bytes().get(index).is_some_and(|b| valid.contains(b));
+
+    if !(separator(4, b"-") && separator(7, b"-")) {
+        return None;
+    }
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    if !(1..=12).contains(&month) || !(1..=days_in_month(year, month)).contains(&day) {
+        return None;
+    }
+
+    let date = days_from_civil(year, month, day) as f64 * DAY;
+    if s.len() == 10 {
+        return Some(date);
+    }
+
+    if !(separator(10, b"Tt") && separator(13, b":") && separator(16, b":")) {
+        return None;
+    }
+
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if hour > 23 || minute > 59 || second > 60 {
+        return None;
+    }
+
+    let mut rest = &s[19..];
+    let mut fraction = 0.;
+    if let Some(digits) = rest.strip_prefix('.') {
+        let length = digits.bytes().take_while(u8::is_ascii_digit).count();
+        if length == 0 {
+            return None;
+        }
+
+        fraction = format!("0.{}", &digits[..length]).parse().ok()?;
+        rest = &digits[length..];
+    }
+
+    let offset = match rest.as_bytes() {
+        [b'Z' | b'z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let digits = [h1, h2, m1, m2];
+            if !digits.iter().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+
+            let [h1, h2, m1, m2] = digits.map(|b| i64::from(b - b'0'));
+            let offset = (h1 * 10 + h2) * 60 + m1 * 10 + m2;
+            if *sign == b'-' { -offset } else { offset }
+        }
+        _ => return None,
+    };
+
+    let time = ((hour * 60 + minute - offset) * 60 + second) as f64 + fraction;
+    Some(date + time)
+}
+
+fn is_leap_year(year: i64) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i64, month: i64) -> i64 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch for a date in the proleptic Gregorian calendar
+///
+/// See <https://howardhinnant.github.io/date_algorithms.html#days_from_civil>.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The date for a number of days since the Unix epoch (the inverse of [`days_from_civil`])
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Combine the values of samples into `count` evenly spaced buckets between the first and last
+/// timestamp
+///
+/// Buckets without any values are left empty.
+#[must_use]
+pub fn bucket(
+    samples: &[(Timestamp, Option<f64>)],
+    count: usize,
+    aggregate: Aggregate,
+) -> Vec<Option<f64>> {
+    let Some(axis) = Axis::from_samples(samples).filter(|_| count > 0) else {
+        return vec![];
+    };
+
+    let mut buckets: Vec<Vec<f64>> = vec![vec![]; count];
+    for (timestamp, value) in samples {
+        if let Some(value) = value {
+            buckets[axis.position(*timestamp, count)].push(*value);
+        }
+    }
+
+    buckets
+        .into_iter()
+        .map(|values| aggregate.combine(values))
+        .collect()
+}
+
+/// The span of time shown along the x axis
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Axis {
+    start: f64,
+    end: f64,
+}
+
+impl Axis {
+    /// The span of time covered by the samples, if there are any
+    pub fn from_samples(samples: &[(Timestamp, Option<f64>)]) -> Option<Self> {
+        samples.iter().fold(None, |axis, (Timestamp(time), _)| {
+            Some(match axis {
+                None => Self {
+                    start: *time,
+                    end: *time,
+                },
+                Some(Self { start, end }) => Self {
+                    start: start.min(*time),
+                    end: end.max(*time),
+                },
+            })
+        })
+    }
+
+    /// Which of `count` evenly spaced positions a time falls in
+    fn position(self, Timestamp(time): Timestamp, count: usize) -> usize {
+        let span = self.end - self.start;
+        if span <= 0. {
+            return 0;
+        }
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let position = ((time - self.start) / span * count as f64).floor() as usize;
+        position.min(count - 1)
+    }
+
+    /// Labels for evenly spaced, round times along an axis `width` characters wide
+    #[must_use]
+    pub fn labels(self, width: usize) -> String {
+        if self.end <= self.start {
+            return Step::Seconds(1.).format(self.start);
+        }
+
+        let years = (0..16).flat_map(|power| {
+            [1, 2, 5].map(|years| Step::Months(12 * years * 10_usize.pow(power)))
+        });
+
+        // Steps with more ticks than columns can't fit, so don't bother finding them all
+        let ticks = STEPS
+            .into_iter()
+            .chain(years)
+            .filter(|step| (self.end - self.start) / step.seconds() <= width as f64)
+            .map(|step| (step, self.ticks(step)))
+            .find(|(step, ticks)| ticks.len() * (step.label_length() + 1) <= width);
+
+        let Some((step, ticks)) = ticks else {
+            return String::new();
+        };
+
+        let mut line = String::new();
+        for time in ticks {
+            let column = self.position(Timestamp(time), width);
+            let label = step.format(time);
+            let padding = column.saturating_sub(line.chars().count());
+
+            // Skip labels that would overlap the one before it or run past the end
+            if (padding > 0 || line.is_empty()) && column + label.len() <= width {
+                line.push_str(&" ".repeat(padding));
+                line.push_str(&label);
+            }
+        }
+
+        line
+    }
+
+    /// Every multiple of the step between the start and end of the axis
+    fn ticks(self, step: Step) -> Vec<f64> {
+        match step {
+            Step::Seconds(seconds) => {
+                let first = (self.start / seconds).ceil() as i64;
+                let last = (self.end / seconds).floor() as i64;
+                (first..=last).map(|tick| tick as f64 * seconds).collect()
+            }
+            Step::Months(months) => {
+                let months = months as i64;
+                let (year, month, _) = civil_from_days(self.start.div_euclid(DAY) as i64);
+                let mut tick = (year * 12 + month - 1).div_euclid(months) * months;
+
+                let mut ticks = vec![];
+                loop {
+                    let time = days_from_civil(tick.div_euclid(12), tick.rem_euclid(12) + 1, 1)
+                        as f64
+                        * DAY;
+                    if time > self.end {
+                        break ticks;
+                    }
+
+                    if time >= self.start {
+                        ticks.push(time);
+                    }
+
+                    tick += months;
+                }
+            }
+        }
+    }
+}
+
+/// The distance between ticks along the axis
+#[derive(Debug, Clone, Copy)]
+enum Step {
+    Seconds(f64),
+    Months(usize),
+}
+
+const STEPS: [Step; 24] = [
+    Step::Seconds(1.),
+    Step::Seconds(2.),
+    Step::Seconds(5.),
+    Step::Seconds(10.),
+    Step::Seconds(15.),
+    Step::Seconds(30.),
+    Step::Seconds(MINUTE),
+    Step::Seconds(2. * MINUTE),
+    Step::Seconds(5. * MINUTE),
+    Step::Seconds(10. * MINUTE),
+    Step::Seconds(15. * MINUTE),
+    Step::Seconds(30. * MINUTE),
+    Step::Seconds(HOUR),
+    Step::Seconds(2. * HOUR),
+    Step::Seconds(3. * HOUR),
+    Step::Seconds(6. * HOUR),
+    Step::Seconds(12. * HOUR),
+    Step::Seconds(DAY),
+    Step::Seconds(2. * DAY),
+    Step::Seconds(7. * DAY),
+    Step::Seconds(14. * DAY),
+    Step::Months(1),
+    Step::Months(3),
+    Step::Months(6),
+];
+
+impl Step {
+    /// Roughly how long the step is
+    fn seconds(self) -> f64 {
+        match self {
+            Self::Seconds(seconds) => seconds,
+            Self::Months(months) => months as f64 * 30. * DAY,
+        }
+    }
+
+    fn label_length(self) -> usize {
+        self.format(0.).len()
+    }
+
+    /// Show only as much of the time as changes between ticks
+    fn format(self, time: f64) -> String {
+        let days = time.div_euclid(DAY) as i64;
+        let seconds = time.rem_euclid(DAY) as i64;
+        let (year, month, day) = civil_from_days(days);
+        let (hour, minute, second) = (seconds / 3600, seconds / 60 % 60, seconds % 60);
+
+        match self {
+            Self::Seconds(step) if step < MINUTE => format!("{hour:02}:{minute:02}:{second:02}"),
+            Self::Seconds(step) if step < DAY => format!("{hour:02}:{minute:02}"),
+            Self::Seconds(_) => format!("{month:02}-{day:02}"),
+            Self::Months(months) if months.is_multiple_of(12) => format!("{year}"),
+            Self::Months(_) => format!("{year}-{month:02}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_timestamps() {
+        let parse = |s: &str| s.parse::<Timestamp>().map(|Timestamp(time)| time);
+
+        assert_eq!(Ok(1_700_000_000.5), parse("1700000000.5"));
+        assert_eq!(Ok(0.), parse("1970-01-01T00:00:00Z"));
+        assert_eq!(Ok(951_782_400.), parse("2000-02-29"));
+        assert_eq!(Ok(1_714_571_100.25), parse("2024-05-01T13:45:00.25Z"));
+        assert_eq!(Ok(1_714_571_100.), parse("2024-05-01T15:45:00+02:00"));
+        assert_eq!(Ok(1_714_571_100.), parse("2024-05-01t11:15:00-02:30"));
+
+        for invalid in [
+            "2023-02-29",
+            "2024-13-01",
+            "2024-05-01T24:00:00Z",
+            "2024-05-01T13:45:00",
+            "2024-05-01T13:45:00.Z",
+            "2024-05-01 13:45:00Z",
+            "yesterday",
+            "inf",
+        ] {
+            assert!(parse(invalid).is_err(), "{invalid} should be invalid");
+        }
+    }
+
+    #[test]
+    fn civil_round_trip() {
+        for days in [-719_468, -1, 0, 11_016, 19_844, 2_932_896] {
+            let (year, month, day) = civil_from_days(days);
+            assert_eq!(days, days_from_civil(year, month, day));
+        }
+
+        assert_eq!((2024, 2, 29), civil_from_days(19_782));
+    }
+
+    #[test]
+    fn bucket_samples() {
+        let samples = [
+            (Timestamp(0.), Some(1.)),
+            (Timestamp(1.), Some(3.)),
+            (Timestamp(5.), None),
+            (Timestamp(9.), Some(2.)),
+            (Timestamp(10.), Some(4.)),
+        ];
+
+        assert_eq!(
+            vec![Some(2.), None, None, None, Some(3.)],
+            bucket(&samples, 5, Aggregate::Avg)
+        );
+        assert_eq!(
+            vec![Some(1.), None, None, None, Some(2.)],
+            bucket(&samples, 5, Aggregate::Min)
+        );
+        assert_eq!(
+            vec![Some(3.), None, None, None, Some(4.)],
+            bucket(&samples, 5, Aggregate::Max)
+        );
+        assert!(bucket(&[], 5, Aggregate::Avg).is_empty());
+    }
+
+    #[test]
+    fn axis_labels() {
+        let axis = |start: &str, end: &str| Axis {
+            start: start.parse::<Timestamp>().unwrap().0,
+            end: end.parse::<Timestamp>().unwrap().0,
+        };
+
+        assert_eq!(
+            "12:00  12:15   12:30  12:45",
+            axis("2024-05-01T12:00:00Z", "2024-05-01T13:00:00Z").labels(30)
+        );
+        assert_eq!(
+            "   05-02  05-03",
+            axis("2024-05-01T12:00:00Z", "2024-05-04T12:00:00Z").labels(20)
+        );
+        assert_eq!(
+            "   2022   2023",
+            axis("2021-06-01", "2024-06-01").labels(20)
+        );
+        assert_eq!(
+            "12:00:00",
+            axis("2024-05-01T12:00:00Z", "2024-05-01T12:00:00Z").labels(20)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Values written with suffixes and units, like the output of `du`, `ping`, or `ps`.
+//!
+//! With `--units`, each value can end with an SI (`k`, `M`, `G`, ...) or IEC (`Ki`, `Mi`, `Gi`,
+//! ...) prefix, a size in bytes (`kB`, `MiB`, ...), a duration (`ns` up to `d`), or a
+//! percentage. Values are read in their base unit (bytes or seconds), and the labels around the
+//! graph are written back in the unit that fits them best.
+//!
+//! # Example
+//!
+//! ```console
+//! $ printf '%s\n' 120ms 250ms 1.2s 800ms | braille -C --units --legend latency 4
+//!   █
+//!   █▄
+//!   ██
+//! ▁▄██
+//! latency: min 120ms, max 1.2s, last 800ms
+//! ```
+
+use std::fmt;
+
+/// What a value measures, if it was written with a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Bytes,
+    Seconds,
+    Percent,
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Unit::Bytes => write!(f, "bytes"),
+            Unit::Seconds => write!(f, "seconds"),
+            Unit::Percent => write!(f, "percent"),
+        }
+    }
+}
+
+const SI_PREFIXES: [&str; 5] = ["k", "M", "G", "T", "P"];
+
+const IEC_PREFIXES: [&str; 5] = ["Ki", "Mi", "Gi", "Ti", "Pi"];
+
+/// Each duration, from longest to shortest, with how many seconds it is
+const DURATIONS: [(&str, f64); 7] = [
+    ("d", 86_400.),
+    ("h", 3600.),
+    ("min", 60.),
+    ("s", 1.),
+    ("ms", 1e-3),
+    ("µs", 1e-6),
+    ("ns", 1e-9),
+];
+
+/// Parse a value with an optional suffix, giving it in its base unit
+///
+/// Plain numbers (including ones like `1.2e3`) are parsed as they are, without a unit.
+#[must_use]
+pub fn parse(s: &str) -> Option<(f64, Option<Unit>)> {
+    if let Ok(value) = s.parse() {
+        return Some((value, None));
+    }
+
+    let number = s.trim_end_matches(|c: char| c.is_alphabetic() || c == '%');
+    let (factor, unit) = suffix(&s[number.len()..])?;
+    let value: f64 = number.trim_end().parse().ok()?;
+    Some((value * factor, unit))
+}
+
+/// How much a suffix multiplies a value by, and the unit it's in
+fn suffix(s: &str) -> Option<(f64, Option<Unit>)> {
+    let prefix = |s: &str| -> Option<f64> {
+        let power = |prefixes: &[&str]| {
+            prefixes
+                .iter()
+                .zip(1..)
+                .find_map(|(prefix, power)| (*prefix == s).then_some(power))
+        };
+
+        if s.is_empty() {
+            Some(1.)
+        } else if s == "K" {
+            Some(1e3)
+        } else if let Some(power) = power(&SI_PREFIXES) {
+            Some(1000_f64.powi(power))
+        } else {
+            power(&IEC_PREFIXES).map(|power| 1024_f64.powi(power))
+        }
+    };
+
+    match s {
+        "%" => Some((1., Some(Unit::Percent))),
+        "m" => Some((60., Some(Unit::Seconds))),
+        "us" => Some((1e-6, Some(Unit::Seconds))),
+        _ => {
+            if let Some((_, seconds)) = DURATIONS.iter().find(|(name, _)| *name == s) {
+                Some((*seconds, Some(Unit::Seconds)))
+            } else if let Some(bytes) = s.strip_suffix('B') {
+                prefix(bytes).map(|factor| (factor, Some(Unit::Bytes)))
+            } else {
+                prefix(s)
+                    .filter(|_| !s.is_empty())
+                    .map(|factor| (factor, None))
+            }
+        }
+    }
+}
+
+/// Write a value for a label, in the unit that fits it best
+///
+/// Values without a unit are written as they are.
+#[must_use]
+pub fn label(value: f64, unit: Option<Unit>) -> String {
+    let short = |value: f64| ((value * 100.).round() / 100.).to_string();
+
+    match unit {
+        None => value.to_string(),
+        Some(Unit::Percent) => format!("{}%", short(value)),
+        Some(Unit::Seconds) => {
+            let (name, seconds) = DURATIONS
+                .iter()
+                .find(|(_, seconds)| value.abs() >= *seconds)
+                .unwrap_or(&("s", 1.));
+            format!("{}{name}", short(value / seconds))
+        }
+        Some(Unit::Bytes) => {
+            let (prefix, factor) = IEC_PREFIXES
+                .iter()
+                .zip(1..)
+                .map(|(prefix, power)| (*prefix, 1024_f64.powi(power)))
+                .take_while(|(_, factor)| value.abs() >= *factor)
+                .last()
+                .unwrap_or(("", 1.));
+            format!("{}{prefix}B", short(value / factor))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_suffixes() {
+        assert_eq!(Some((1200., None)), parse("1.2e3"));
+        assert_eq!(Some((12_000., None)), parse("12k"));
+        assert_eq!(Some((2048., None)), parse("2Ki"));
+        assert_eq!(
+            Some((3.5 * 1024. * 1024., Some(Unit::Bytes))),
+            parse("3.5MiB")
+        );
+        assert_eq!(Some((1.5e9, Some(Unit::Bytes))), parse("1.5 GB"));
+        assert_eq!(Some((0.25, Some(Unit::Seconds))), parse("250ms"));
+        assert_eq!(Some((90., Some(Unit::Seconds))), parse("1.5m"));
+        assert_eq!(Some((45., Some(Unit::Percent))), parse("45%"));
+        assert_eq!(None, parse("12 apples"));
+        assert_eq!(None, parse("ms"));
+    }
+
+    #[test]
+    fn label_in_best_unit() {
+        assert_eq!("1.5", label(1.5, None));
+        assert_eq!("250ms", label(0.25, Some(Unit::Seconds)));
+        assert_eq!("2min", label(120., Some(Unit::Seconds)));
+        assert_eq!("0s", label(0., Some(Unit::Seconds)));
+        assert_eq!("3.4MiB", label(3.4 * 1024. * 1024., Some(Unit::Bytes)));
+        assert_eq!("512B", label(512., Some(Unit::Bytes)));
+        assert_eq!("45%", label(45., Some(Unit::Percent)));
+    }
+}
@@ -0,0 +1,214 @@
+//! Graph the output of a command that's run again and again, like `watch`.
+//!
+//! Each line the command prints is added to a rolling history, which only keeps as many lines as
+//! fit in the graph. The graph is drawn from scratch after every run, with the same options.
+
+use std::collections::VecDeque;
+use std::io::{IsTerminal, LineWriter, prelude::*};
+use std::process::{Command, Stdio};
+use std::rc::Rc;
+use std::time::Instant;
+
+use anyhow::Context;
+use crossterm::cursor::{Hide, MoveTo, MoveToNextLine, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::style::Print;
+use crossterm::terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::{execute, queue};
+
+use crate::legend;
+use crate::opt::{Opt, Orientation};
+use crate::units::{self, Unit};
+use crate::util;
+
+/// Run the command and redraw the graph of its output until the user quits or the command fails
+pub fn run<W: Write>(opt: Opt, mut writer: LineWriter<W>) -> anyhow::Result<()> {
+    let interval = opt.watch.expect("should only watch with an interval").0;
+    let mut history = History::default();
+
+    // Without a terminal to redraw in, each graph is printed after the last
+    if !std::io::stdout().is_terminal() {
+        loop {
+            history.push(&run_command(&opt.command)?, capacity(&opt)?);
+            if history.runs > 1 {
+                writeln!(writer)?;
+            }
+            writer.write_all(draw(&opt, &history)?.as_bytes())?;
+            writer.flush()?;
+            std::thread::sleep(interval);
+        }
+    }
+
+    let header = format!(
+        "Every {}: {}",
+        units::label(interval.as_secs_f64(), Some(Unit::Seconds)),
+        opt.command
+            .iter()
+            .map(|arg| arg.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ")
+    );
+
+    let mut screen = Screen::enter(&mut writer)?;
+    let mut next_run = Instant::now();
+    loop {
+        if Instant::now() >= next_run {
+            history.push(&run_command(&opt.command)?, capacity(&opt)?);
+            next_run = Instant::now() + interval;
+        }
+
+        let width = usize::from(terminal::size()?.0);
+        screen.draw(&header, &draw(&opt, &history)?, width)?;
+
+        // Wait for the next run, unless a key is pressed first
+        if !event::poll(next_run.saturating_duration_since(Instant::now()))? {
+            continue;
+        }
+
+        let Event::Key(key) = event::read()? else {
+            // Anything else (like the terminal being resized) just redraws the graph
+            continue;
+        };
+
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => break,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// The lines printed by the command so far, keeping only the latest ones
+#[derive(Debug, Default)]
+struct History {
+    lines: VecDeque<String>,
+    runs: usize,
+}
+
+impl History {
+    /// Add the output of a run, dropping the oldest lines beyond `capacity`
+    fn push(&mut self, output: &str, capacity: usize) {
+        self.lines.extend(output.lines().map(String::from));
+        self.lines
+            .drain(..self.lines.len().saturating_sub(capacity));
+        self.runs += 1;
+    }
+
+    /// The lines kept, as input to graph
+    fn input(&self) -> String {
+        self.lines.iter().fold(String::new(), |mut input, line| {
+            input.push_str(line);
+            input.push('\n');
+            input
+        })
+    }
+}
+
+/// How many lines fit in the graph: one for each value across a column graph, or each row of a
+/// bar graph
+fn capacity(opt: &Opt) -> anyhow::Result<usize> {
+    let (width, height) = util::get_terminal_size()?;
+    let lines = match opt.kind().orientation() {
+        Orientation::Vertical => width * opt.kind().char_type().values_per_column(),
+        // Leave room for the header
+        Orientation::Horizontal => height.saturating_sub(1),
+    };
+
+    Ok(usize::from(lines).max(1))
+}
+
+/// Run the command once, returning what it printed
+fn run_command(command: &[std::ffi::OsString]) -> anyhow::Result<String> {
+    let (program, args) = command
+        .split_first()
+        .expect("should have a command to watch");
+
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .with_context(|| format!("Failed to run {}", program.to_string_lossy()))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let error = anyhow::anyhow!("The command exited with {}", output.status);
+        return Err(match stderr.trim() {
+            "" => error,
+            stderr => error.context(stderr.to_string()),
+        });
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Draw the graph of the history with a fresh copy of the options
+fn draw(opt: &Opt, history: &History) -> anyhow::Result<String> {
+    let mut opt = opt.clone();
+    opt.skipped_lines = Rc::default();
+
+    let input = Box::new(std::io::Cursor::new(history.input()));
+    let mut buffer = vec![];
+    crate::print_graph_kind(opt, input, LineWriter::new(&mut buffer))?;
+
+    Ok(String::from_utf8(buffer)?)
+}
+
+/// The terminal in raw mode on the alternate screen, which is put back the way it was when
+/// dropped (even if running the command fails)
+struct Screen<'a, W: Write> {
+    writer: &'a mut W,
+}
+
+impl<'a, W: Write> Screen<'a, W> {
+    fn enter(writer: &'a mut W) -> std::io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        let screen = Self { writer };
+        execute!(screen.writer, EnterAlternateScreen, Hide)?;
+        Ok(screen)
+    }
+
+    /// Draw the header with the graph below it
+    fn draw(&mut self, header: &str, graph: &str, width: usize) -> std::io::Result<()> {
+        queue!(
+            self.writer,
+            MoveTo(0, 0),
+            Clear(ClearType::All),
+            Print(legend::truncate(header, width)),
+        )?;
+
+        for line in graph.lines() {
+            let line: String = line.chars().take(width).collect();
+            queue!(self.writer, MoveToNextLine(1), Print(line))?;
+        }
+
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Drop for Screen<'_, W> {
+    fn drop(&mut self) {
+        // There's nothing left to do if this fails, so don't hide the error that got us here
+        let _ = execute!(self.writer, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn history_keeps_latest_lines() {
+        let mut history = History::default();
+        history.push("1\n2\n", 3);
+        history.push("3\n4\n", 3);
+        assert_eq!("2\n3\n4\n", history.input());
+        assert_eq!(2, history.runs);
+    }
+}
@@ -343,6 +343,39 @@ fn braille_line_line_style_all_negative() {
     assert!(stderr.is_empty());
 }
 
+#[test]
+fn graphs_from_file_match_stdin() {
+    // Files are read twice, once to find the range and again to draw them
+    for (fixture, args) in [
+        (
+            "two_series_bad_line.tsv",
+            &[
+                "--header",
+                "-p2",
+                "--on-error",
+                "skip",
+                "--independent-scale",
+                "8",
+            ][..],
+        ),
+        (
+            "two_series_bad_line.tsv",
+            &["--header", "-p2", "--on-error", "skip", "-c", "4"],
+        ),
+        ("single_all_negative.tsv", &["--baseline", "mean", "8"]),
+        (
+            "single_all_negative.tsv",
+            &["--baseline", "mean", "-k", "octant-columns", "4"],
+        ),
+    ] {
+        let path = format!("{}/tests/{fixture}", env!("CARGO_MANIFEST_DIR"));
+        let input = std::fs::read_to_string(&path).unwrap();
+        let from_file = util::get_output(["-f", path.as_str()].iter().chain(args));
+        let from_stdin = util::get_output_from_str(input.trim_end(), args);
+        assert_eq!(from_stdin, from_file, "{fixture} {args:?}");
+    }
+}
+
 #[test]
 fn invalid_input_single() {
     let input = "hello";
@@ -380,3 +413,732 @@ fn braille_3_values_per_line() {
     insta::assert_snapshot!(stdout);
     insta::assert_snapshot!(stderr);
 }
+
+#[test]
+fn stacked_columns() {
+    let (stdout, stderr) =
+        util::get_output_from_str(util::THREE_SERIES, ["--stack", "-p3", "-c", "4"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn stacked_bars() {
+    let (stdout, stderr) =
+        util::get_output_from_str(util::TWO_SERIES, ["--stack", "-p2", "-b", "6"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn stacked_sextant_columns() {
+    let (stdout, stderr) = util::get_output_from_str(
+        util::THREE_SERIES,
+        ["--stack", "-p3", "-k", "sextant-columns", "4"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn octant_columns_series_3() {
+    let (stdout, stderr) = util::get_output_from_str(
+        util::THREE_SERIES,
+        ["-p3", "-k", "octant-columns", "-s", "line", "4"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn stacked_block_columns() {
+    let (stdout, stderr) = util::get_output_from_str(
+        util::THREE_SERIES,
+        ["--stack", "-p3", "-C", "--legend", "a,b,c", "4"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn stacked_block_bars() {
+    let input = "1 2\n2 2\n3 -1\n4 0";
+
+    let (stdout, stderr) = util::get_output_from_str(input, ["--stack", "-p2", "-B", "6"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn stacked_outside_range() {
+    let input = "1 2\n3 4";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["--stack", "-p2", "-b", "-r", "0:5", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn grouped_braille_bars() {
+    let (stdout, stderr) =
+        util::get_output_from_str(util::TWO_SERIES, ["--group", "-p2", "-r", "0:4", "-b", "6"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grouped_block_bars() {
+    let (stdout, stderr) =
+        util::get_output_from_str(util::TWO_SERIES, ["--group", "-p2", "-r", "0:4", "-B", "6"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn reference_lines_columns() {
+    let input = (1..=12)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-c", "3", "--hline", "5:SLO", "--hline", "10"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn reference_lines_bars() {
+    let input = (1..=9)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        ["-b", "6", "--vline", "4:four", "--vline", "7:seven"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn reference_lines_wrong_orientation() {
+    let (stdout, stderr) = util::get_output_from_str("1", ["-c", "3", "--vline", "1"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn baseline_value_columns() {
+    let input = (-3..=4)
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (stdout, stderr) = util::get_output_from_str(input, ["-c", "2", "--baseline", "2"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn baseline_median_auto_style_bars() {
+    let input = "1\n5\n9\n3\n7\n2";
+
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-b", "4", "-sa", "--baseline", "median"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn baseline_two_series() {
+    let (stdout, stderr) =
+        util::get_output_from_str("1 2\n3 4", ["-c", "-p2", "--baseline", "mean", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn baseline_block_columns() {
+    let input = "1\n5\n9\n3";
+
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-C", "3", "-r", "0:10", "--baseline", "4"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn title_and_legend_stacked() {
+    let input = (1..=8)
+        .map(|x| format!("{x} {}", 9 - x))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "-c", "2", "-p2", "--stack", "--title", "Totals", "--legend", "up,down",
+        ],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn legend_from_header_comma_separated() {
+    let input = "cpu,mem\n1,2\n3,4\n2,9";
+
+    let (stdout, stderr) = util::get_output_from_str(input, ["-b", "12", "-p2", "--header"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn legend_from_header_truncated() {
+    let input = "cpu,mem\n1 2\n3 4\n2 9";
+
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "-b",
+            "12",
+            "-p2",
+            "--header",
+            "--title",
+            "Resources used over time",
+        ],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn config_preset() {
+    let input = "1\n3\n5\n7\n9\n7\n5\n3";
+
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["--config", "presets.toml", "--preset", "latency"]);
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn config_preset_overridden() {
+    let input = "1\n3\n5\n7\n9\n7\n5\n3";
+
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "--config",
+            "presets.toml",
+            "--preset",
+            "latency",
+            "-s",
+            "line",
+            "--hline",
+            "2",
+            "3",
+        ],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn config_preset_not_found() {
+    let (stdout, stderr) =
+        util::get_output_from_str("1", ["--config", "presets.toml", "--preset", "missing"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn config_preset_invalid() {
+    let (stdout, stderr) =
+        util::get_output_from_str("1", ["--config", "presets.toml", "--preset", "bad"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn time_series_unsupported_kind() {
+    let (stdout, stderr) =
+        util::get_output_from_str("2024-05-01T12:00:00Z 1", ["--time", "-b", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn tiles_shared_range() {
+    let input = r"name,up,down
+1,8,4
+2,7,3
+3,6,2
+4,5,1
+5,4,1
+6,3,2
+7,2,3
+8,1,4";
+
+    let (stdout, stderr) = util::get_output_from_str_with_width(
+        26,
+        input,
+        ["--tile", "--header", "-r", "0:", "-c", "8"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn tiles_independent_range() {
+    let input = "1 10\n2 20\n3 30\n4 40";
+
+    let (stdout, stderr) = util::get_output_from_str_with_width(
+        10,
+        input,
+        ["--tile", "2x1", "--tile-range", "independent", "-C", "6"],
+    );
+
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn tiles_too_many_series() {
+    let (stdout, stderr) = util::get_output_from_str("1 2 3", ["--tile", "1x2", "-c", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn tiles_too_short() {
+    let (stdout, stderr) =
+        util::get_output_from_str("1 2 3", ["--tile", "2x2", "--title", "Numbers", "-c", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn interactive_without_terminal() {
+    let input = "1\n2\n3\n4\n3\n2\n1";
+    let args = ["-c", "-r", "0:4", "2"];
+
+    let (expected, _) = util::get_output_from_str(input, args);
+    let (stdout, stderr) = util::get_output_from_str(input, ["-i"].into_iter().chain(args));
+
+    assert_eq!(expected, stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn interactive_unsupported_kind() {
+    let (stdout, stderr) = util::get_output_from_str("1", ["-i", "-b", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn annotate_columns() {
+    let (stdout, stderr) = util::get_output_from_str("1\n3\n8\n4\n2\n5", ["-C", "--annotate", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn annotate_braille_columns() {
+    let input = "3\n1\n4\n1\n5\n9\n2\n6\n5\n3\n5\n8\n9\n7\n9\n3";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-c", "--annotate", "5"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn annotate_grid() {
+    let input = "0 0\n1 3\n2 -1\n3 2\n4 1";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-c", "-g", "24", "12", "--annotate"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn annotate_grid_given_bounds() {
+    // The last point is outside the bounds, so it's neither drawn nor marked
+    let input = "0 0\n1 3\n2 -1\n3 2\n4 1\n5 9";
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        ["-g", "24", "12", "-x", "0:4", "-y", "-1:3", "--annotate"],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn annotate_independent_scale() {
+    let input = "100 1\n200 2\n300 3\n150 2";
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        ["-c", "-p2", "--independent-scale", "--annotate", "5"],
+    );
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn annotate_unsupported_kind() {
+    let (stdout, stderr) = util::get_output_from_str("1", ["--annotate", "-b", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn independent_scale() {
+    let input = "100 1\n200 0\n400 3\n300 2";
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "-c",
+            "-p2",
+            "-s",
+            "line",
+            "--independent-scale",
+            "--legend",
+            "requests,errors",
+            "4",
+        ],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn invalid_utf8() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let input = std::ffi::OsStr::from_bytes(b"1\n\xff2\n3");
+    let (stdout, stderr) = util::get_output_from_str(input, ["-c", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn invalid_utf8_lossy() {
+    use std::os::unix::ffi::OsStrExt;
+
+    let input = std::ffi::OsStr::from_bytes(b"caf\xe9\n1\n3\n2");
+    let (stdout, stderr) = util::get_output_from_str(input, ["-c", "--lossy", "--header", "2"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn on_error_skip() {
+    let input = "1\n2\nN/A\n4\n1 2";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-C", "--on-error", "skip", "4"]);
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn on_error_null() {
+    let input = "1\n2\nN/A\n4";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-C", "--on-error", "null", "4"]);
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn invalid_input_position() {
+    let (stdout, stderr) = util::get_output_from_str("1 2\n3 4x", ["-c", "-p2", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn null_values_and_comments() {
+    let input = "# requests per second\n1\n2\nN/A\n-\nNaN\n4\n3";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-C", "--null-values=N/A,-", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn non_finite_clip() {
+    let input = "1\n2\ninf\n3\n-inf\n4";
+    let (stdout, stderr) = util::get_output_from_str(input, ["-C", "--non-finite", "clip", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_given_bounds_match_found_bounds() {
+    // With every bound given, points are drawn as they're read instead of being kept
+    let input = "0 0\n1 3\n2 -1\n3 2\n4 1";
+    let (found, _) = util::get_output_from_str(input, ["-g", "16", "8"]);
+    let (given, stderr) = util::get_output_from_str(
+        format!("{input}\n5 9\n-1 0"),
+        ["-g", "16", "8", "-x", "0:4", "-y", "-1:3"],
+    );
+    assert_eq!(found, given);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_comments() {
+    let input = "// points\n0 0\n1 1\n2 4\n3 9";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-g", "8", "16", "--comment-prefix", "//"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_sextants() {
+    let input = "0 0\n1 1\n2 4\n3 9\n4 16";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["--kind", "sextant-bars", "-g", "10", "12"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_quadrants() {
+    let input = "0 0\n1 1\n2 4\n3 9\n4 16";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["--kind", "mini-columns", "-g", "10", "12"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_polar_degrees() {
+    // A circle, given as an angle and radius every 15 degrees
+    let input = (0..24)
+        .map(|i| format!("{} 1", i * 15))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (stdout, stderr) = util::get_output_from_str(input, ["-g", "16", "--polar", "degrees"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_parametric() {
+    let input = (0..=628)
+        .map(|i| (f64::from(i) / 100.).to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        ["-g", "32", "16", "--parametric", "sin(3*t)", "sin(2*t)"],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_parametric_polar_rose() {
+    let input = (0..=628)
+        .map(|i| (f64::from(i) / 100.).to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "-g",
+            "24",
+            "-G",
+            "-1:1",
+            "--parametric",
+            "t",
+            "cos(2*t)",
+            "--polar",
+        ],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn grid_parametric_invalid_expression() {
+    let (stdout, stderr) = util::get_output_from_str("1", ["-g", "--parametric", "sin(t", "t"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn units_legend() {
+    let input = "120ms\n250ms\n1.2s\n800ms";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-C", "--units", "--legend", "latency", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn units_annotate() {
+    let input = "512KiB\n3.4MiB\n1.5MiB\n2MiB\n700KiB";
+    let (stdout, stderr) =
+        util::get_output_from_str_with_width(20, input, ["-C", "--units", "--annotate", "6"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn units_mixed() {
+    let (stdout, stderr) = util::get_output_from_str("12%\n45%\n1s", ["-C", "--units", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn pattern_log_lines() {
+    let input = "GET / took 12ms\nserver started\nGET /a took 40ms\nGET /b took 25ms";
+    let (stdout, stderr) =
+        util::get_output_from_str(input, ["-C", "--pattern", r"took (\d+)ms", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn pattern_named_groups() {
+    let input = "q=1 w=5\nq=3 w=2\nreloading\nq=4 w=4";
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        [
+            "-c",
+            "-p2",
+            "--pattern",
+            r"q=(?<queue>\d+) w=(?<workers>\d+)",
+            "--unmatched",
+            "gap",
+            "4",
+        ],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn pattern_wrong_number_of_groups() {
+    let (stdout, stderr) = util::get_output_from_str("1 2", ["-C", "--pattern", r"(\d) (\d)", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn multiple_files_as_series() {
+    let before = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/before.txt");
+    let after = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/after.txt");
+
+    // The shorter file is padded with gaps
+    let (stdout, stderr) = util::get_output(["-f", before, "-f", after, "-c", "4"]);
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn multiple_files_skip_invalid_lines() {
+    let before = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/before.txt");
+
+    // The second line is skipped in both files, so they stay lined up
+    let (stdout, stderr) = util::get_output_from_str(
+        "2\nfoo\n3\n2\n1",
+        ["-f", before, "-f", "-", "--on-error", "skip", "-c", "4"],
+    );
+    insta::assert_snapshot!(stdout);
+    assert_eq!("Warning: 1 line couldn't be parsed: 2\n", stderr);
+}
+
+#[test]
+fn multiple_files_comment_in_one() {
+    let before = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/before.txt");
+
+    // A comment skips its line in every file, like a line that can't be parsed
+    let (stdout, stderr) =
+        util::get_output_from_str("2\n# a note\n3\n2\n1", ["-f", before, "-f", "-", "-c", "4"]);
+    let (expected, _) = util::get_output_from_str(
+        "2\nfoo\n3\n2\n1",
+        ["-f", before, "-f", "-", "--on-error", "skip", "-c", "4"],
+    );
+    assert_eq!(expected, stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn multiple_files_with_standard_input() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/single_all_negative.tsv");
+    let input = "-9\n-8\n-7\n-6";
+    let (stdout, stderr) = util::get_output_from_str(
+        input,
+        ["-f", "-", "-f", path, "-c", "--legend", "stdin,file", "4"],
+    );
+    insta::assert_snapshot!(stdout);
+    assert!(stderr.is_empty());
+}
+
+#[test]
+fn multiple_files_wrong_per() {
+    let path = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/single_all_negative.tsv");
+    let (stdout, stderr) = util::get_output(["-f", path, "-f", path, "-c", "-p3", "4"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
+
+#[test]
+fn watch_until_command_fails() {
+    let counter = std::env::temp_dir().join(format!("braille-watch-{}", std::process::id()));
+    let _ = std::fs::remove_file(&counter);
+
+    // Print the square of how many times it's run, then fail on the fourth run
+    let script = r#"n=$(($(cat "$0" 2>/dev/null || echo 0) + 1)); echo $n > "$0"; [ $n -le 3 ] || exit 1; echo $((n * n))"#;
+    let (stdout, stderr) = util::get_output_from_str_with_width(
+        2,
+        "",
+        [
+            "-C".as_ref(),
+            "-r".as_ref(),
+            "0:9".as_ref(),
+            "--watch".as_ref(),
+            "0".as_ref(),
+            "3".as_ref(),
+            "--".as_ref(),
+            "sh".as_ref(),
+            "-c".as_ref(),
+            script.as_ref(),
+            counter.as_os_str(),
+        ],
+    );
+    let _ = std::fs::remove_file(&counter);
+
+    // Only the two latest values fit across the graph
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn watch_invalid_interval() {
+    let (stdout, stderr) = util::get_output(["--watch", "soon", "--", "echo", "1"]);
+    insta::assert_snapshot!(stderr);
+    assert!(stdout.is_empty());
+}
@@ -53,3 +53,63 @@ t_modeline!(
 3
 4"
 );
+
+t_modeline!(
+    multiple_sections,
+    r"braille -r 0:4 -c 2
+1
+2
+3
+4
+braille -r 0:4 -b 4
+4
+2"
+);
+
+t_modeline!(
+    comments_before_modeline,
+    r"# latency in ms
+# from the load balancer
+braille -r 0:4 -c 2
+1
+# a comment inside the section
+3
+4"
+);
+
+#[test]
+fn comments_before_invalid_value() {
+    let input = r"# a comment
+braille -r 0:4 -c 2
+1
+hello";
+
+    let (stdout, stderr) = get_output_from_str(input, ["-m"]);
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn multiple_sections_invalid_modeline() {
+    let input = r"braille -r 0:4 -c 2
+1
+2
+braille --foo
+3";
+
+    let (stdout, stderr) = get_output_from_str(input, ["-m"]);
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
+
+#[test]
+fn multiple_sections_invalid_value() {
+    let input = r"braille -r 0:4 -c 2
+1
+braille -r 0:4 -b 4
+hello";
+
+    let (stdout, stderr) = get_output_from_str(input, ["-m"]);
+    insta::assert_snapshot!(stdout);
+    insta::assert_snapshot!(stderr);
+}
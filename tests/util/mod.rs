@@ -1,5 +1,32 @@
 use std::process::{Command, Stdio};
 
+/// Run the binary without picking up the user's config files or environment
+///
+/// It runs from the config fixtures, whose `.braille.toml` is found before any other.
+fn command() -> Command {
+    let bin = concat!(env!("CARGO_MANIFEST_DIR"), "/target/debug/braille"); // bin name
+    let config = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/config");
+    let mut command = Command::new(bin);
+    command
+        .current_dir(config)
+        .env("XDG_CONFIG_HOME", config)
+        .env_remove("BRAILLE_CONFIG")
+        .env_remove("BRAILLE_PRESET")
+        .env_remove("BRAILLE_PER")
+        .env_remove("BRAILLE_STYLE")
+        .env_remove("BRAILLE_USE_FULL_DEFAULT_HEIGHT")
+        // Errors would end with a backtrace, which the snapshots of stderr don't have
+        .env_remove("RUST_BACKTRACE")
+        .env_remove("RUST_LIB_BACKTRACE");
+    command
+}
+
+/// Three series that rise and fall across each other, one value of each on every line
+pub const THREE_SERIES: &str = "1 2 3\n2 2 2\n3 1 2\n4 0 1\n3 1 0\n2 2 1\n1 3 2\n0 4 3";
+
+/// The first two series of [`THREE_SERIES`]
+pub const TWO_SERIES: &str = "1 2\n2 2\n3 1\n4 0\n3 1\n2 2\n1 3\n0 4";
+
 pub fn from_numbers(inputs: &[Option<f64>]) -> String {
     let lines = inputs
         .iter()
@@ -14,13 +41,37 @@ where
     Iter: IntoIterator<Item = S>,
     S: AsRef<std::ffi::OsStr>,
 {
-    let bin = concat!(env!("CARGO_MANIFEST_DIR"), "/target/debug/braille"); // bin name
+    output_from_str(command(), input, args)
+}
+
+/// Like [`get_output_from_str`], but as if the terminal were `width` columns wide
+pub fn get_output_from_str_with_width<In, Iter, S>(
+    width: u16,
+    input: In,
+    args: Iter,
+) -> (String, String)
+where
+    In: AsRef<std::ffi::OsStr>,
+    Iter: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
+    let mut command = command();
+    command.env("COLUMNS", width.to_string());
+    output_from_str(command, input, args)
+}
+
+fn output_from_str<In, Iter, S>(mut command: Command, input: In, args: Iter) -> (String, String)
+where
+    In: AsRef<std::ffi::OsStr>,
+    Iter: IntoIterator<Item = S>,
+    S: AsRef<std::ffi::OsStr>,
+{
     let echo = Command::new("echo")
         .arg(input)
         .stdout(Stdio::piped())
         .spawn()
         .unwrap();
-    let output = Command::new(bin)
+    let output = command
         .args(args)
         .stdin(Stdio::from(echo.stdout.unwrap()))
         .stdout(Stdio::piped())
@@ -39,8 +90,7 @@ where
     Iter: IntoIterator<Item = S>,
     S: AsRef<std::ffi::OsStr>,
 {
-    let bin = concat!(env!("CARGO_MANIFEST_DIR"), "/target/debug/braille"); // bin name
-    let output = Command::new(bin)
+    let output = command()
         .args(args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())